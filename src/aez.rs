@@ -0,0 +1,316 @@
+use aes::cipher::generic_array::GenericArray;
+use aes::hazmat::cipher_round;
+use blake2::digest::consts::U48;
+use blake2::{Blake2b, Digest};
+
+/**
+ * The AEZ v5 authenticated cipher, restricted to messages shorter than
+ * 32 bytes once expanded, enciphered with AEZ-tiny. This is all aezeed
+ * seeds need, their 19 bytes being expanded by 4 bytes.
+ * Blocks are handled as big-endian 128 bits integers.
+ */
+pub struct Aez {
+    i: u128,
+    j: u128,
+    l: u128,
+}
+
+fn double(block: u128) -> u128 {
+    (block << 1) ^ if block >> 127 == 1 { 0x87 } else { 0 }
+}
+
+/**
+ * Multiplies a block by a small integer in GF(2^128)
+ */
+fn multiply(factor: u32, block: u128) -> u128 {
+    let mut result = 0;
+    let mut power = block;
+    let mut factor = factor;
+    while factor != 0 {
+        if factor & 1 == 1 {
+            result ^= power;
+        }
+        power = double(power);
+        factor >>= 1;
+    }
+    result
+}
+
+/**
+ * Reads bits of a byte string, starting at a bit offset,
+ * as the leading bits of a block
+ */
+fn read_bits(bytes: &[u8], offset: usize, length: usize) -> u128 {
+    (0..length)
+        .filter(|bit| {
+            let position = offset + bit;
+            (bytes[position / 8] >> (7 - position % 8)) & 1 == 1
+        })
+        .fold(0, |block, bit| block | 1 << (127 - bit))
+}
+
+/**
+ * Writes the leading bits of a block into a byte string,
+ * starting at a bit offset
+ */
+fn write_bits(bytes: &mut [u8], offset: usize, length: usize, block: u128) {
+    for bit in 0..length {
+        let position = offset + bit;
+        let mask = 1 << (7 - position % 8);
+        if (block >> (127 - bit)) & 1 == 1 {
+            bytes[position / 8] |= mask;
+        } else {
+            bytes[position / 8] &= !mask;
+        }
+    }
+}
+
+/**
+ * Returns a block with only its leading bits set
+ */
+fn leading_ones(length: usize) -> u128 {
+    match length {
+        0 => 0,
+        _ => u128::MAX << (128 - length),
+    }
+}
+
+impl Aez {
+    /**
+     * Derives the three subkeys from a key,
+     * hashed with BLAKE2b unless it is 48 bytes long
+     */
+    pub fn new(key: &[u8]) -> Aez {
+        let extracted: Vec<u8> = match key.len() {
+            48 => key.to_vec(),
+            _ => Blake2b::<U48>::digest(key).to_vec(),
+        };
+        let block = |index: usize| {
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(&extracted[index * 16..(index + 1) * 16]);
+            u128::from_be_bytes(bytes)
+        };
+        Aez {
+            i: block(0),
+            j: block(1),
+            l: block(2),
+        }
+    }
+
+    /**
+     * Four AES rounds keyed with J, I, L and zero
+     */
+    fn aes4(&self, block: u128) -> u128 {
+        let mut state = GenericArray::from(block.to_be_bytes());
+        for key in [self.j, self.i, self.l, 0].iter() {
+            cipher_round(&mut state, &GenericArray::from(key.to_be_bytes()));
+        }
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&state);
+        u128::from_be_bytes(bytes)
+    }
+
+    /**
+     * Offset of the tweakable block cipher E(j, i)
+     */
+    fn offset(&self, j: u32, i: u32) -> u128 {
+        let mut power = self.i;
+        for _ in 0..i.div_ceil(8) {
+            power = double(power);
+        }
+        multiply(j, self.j) ^ power ^ multiply(i % 8, self.l)
+    }
+
+    /**
+     * The tweakable block cipher E(j, i) for j >= 0
+     */
+    fn encipher_block(&self, j: u32, i: u32, block: u128) -> u128 {
+        self.aes4(block ^ self.offset(j, i))
+    }
+
+    /**
+     * Hashes a string of the tweak made of the nonce and additional data
+     */
+    fn hash_string(&self, j: u32, string: &[u8]) -> u128 {
+        let mut sum = 0;
+        let full_blocks = string.len() / 16;
+        for (index, chunk) in string.chunks(16).take(full_blocks).enumerate() {
+            sum ^= self.encipher_block(j, index as u32 + 1, read_bits(chunk, 0, 128));
+        }
+
+        let rest = &string[full_blocks * 16..];
+        if !rest.is_empty() || string.is_empty() {
+            let length = rest.len() * 8;
+            let padded = read_bits(rest, 0, length) | 1 << (127 - length);
+            sum ^= self.encipher_block(j, 0, padded);
+        }
+        sum
+    }
+
+    /**
+     * Hashes the expansion length, nonce and additional data into the tweak
+     */
+    fn hash(&self, nonce: &[u8], data: &[&[u8]], expansion: usize) -> u128 {
+        let mut delta = self.encipher_block(3, 1, (expansion as u128) * 8);
+        delta ^= self.hash_string(4, nonce);
+        for (index, string) in data.iter().enumerate() {
+            delta ^= self.hash_string(5 + index as u32, string);
+        }
+        delta
+    }
+
+    /**
+     * Enciphers or deciphers a message of 1 to 31 bytes with AEZ-tiny
+     */
+    fn tiny(&self, delta: u128, input: &[u8], decipher: bool) -> Vec<u8> {
+        let bytes = input.len();
+        let half = bytes * 4;
+        let mask = leading_ones(half);
+        let pad = 1u128 << (127 - half);
+        let mut input = input.to_vec();
+
+        let (j, rounds) = match bytes {
+            16.. => (6, 8),
+            3.. => (7, 10),
+            2 => (7, 16),
+            _ => (7, 24),
+        };
+
+        // Short messages have their first bit enciphered separately,
+        // so the Feistel network can't be bypassed
+        let fix_first_bit = |block: &mut Vec<u8>| {
+            let message = read_bits(block, 0, bytes * 8);
+            let tweak = self.encipher_block(0, 3, delta ^ (message | 1 << 127));
+            block[0] ^= ((tweak >> 127) as u8) << 7;
+        };
+        if decipher && bytes < 16 {
+            fix_first_bit(&mut input);
+        }
+
+        let tweak = delta ^ self.offset(0, j);
+        let mut left = read_bits(&input, 0, half);
+        let mut right = read_bits(&input, half, half) | pad;
+        let mut round: u128 = if decipher { rounds - 1 } else { 0 };
+        for _ in 0..rounds / 2 {
+            left = (self.aes4(tweak ^ right ^ round) ^ left) & mask | pad;
+            round = if decipher {
+                round.wrapping_sub(1)
+            } else {
+                round + 1
+            };
+            right = (self.aes4(tweak ^ left ^ round) ^ right) & mask | pad;
+            round = if decipher {
+                round.wrapping_sub(1)
+            } else {
+                round + 1
+            };
+        }
+
+        let mut output = vec![0u8; bytes];
+        write_bits(&mut output, 0, half, right);
+        write_bits(&mut output, half, half, left);
+        if !decipher && bytes < 16 {
+            fix_first_bit(&mut output);
+        }
+        output
+    }
+
+    /**
+     * Encrypts a message, expanded by some bytes of authentication.
+     * Returns None if the expanded message is 32 bytes or longer.
+     */
+    pub fn encrypt(
+        &self,
+        nonce: &[u8],
+        data: &[&[u8]],
+        expansion: usize,
+        message: &[u8],
+    ) -> Option<Vec<u8>> {
+        let length = message.len() + expansion;
+        if message.is_empty() || length >= 32 {
+            return None;
+        }
+
+        let delta = self.hash(nonce, data, expansion);
+        let mut expanded = message.to_vec();
+        expanded.resize(length, 0);
+        Some(self.tiny(delta, &expanded, false))
+    }
+
+    /**
+     * Decrypts a ciphertext and checks its authentication bytes.
+     * Returns None if they don't match, or if the ciphertext
+     * is not between 1 and 31 bytes longer than the expansion.
+     */
+    pub fn decrypt(
+        &self,
+        nonce: &[u8],
+        data: &[&[u8]],
+        expansion: usize,
+        ciphertext: &[u8],
+    ) -> Option<Vec<u8>> {
+        if ciphertext.len() <= expansion || ciphertext.len() >= 32 {
+            return None;
+        }
+
+        let delta = self.hash(nonce, data, expansion);
+        let mut message = self.tiny(delta, ciphertext, true);
+        let authentication = message.split_off(ciphertext.len() - expansion);
+        match authentication.iter().all(|&byte| byte == 0) {
+            true => Some(message),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+fn from_hex(hex: &str) -> Vec<u8> {
+    crate::encoding::from_hex(hex).unwrap()
+}
+
+#[test]
+fn test_reference_vector() {
+    let aez = Aez::new(&from_hex(
+        "fd4bbedf38d1f2fc046abfb9425096a2af60d0b537493f57cfdf99a8ec48337e\
+         967528f0868f159565c09ec6a5df2e86",
+    ));
+    let nonce = from_hex("a69df05bf5bb61d2e649aa180af8ae00");
+    let data = [
+        from_hex("55ce9a9516ccc45e428f"),
+        vec![],
+        from_hex("2d21278f95fe9fa1ecdaa98d379ebf"),
+    ];
+    let data: Vec<&[u8]> = data.iter().map(|string| &string[..]).collect();
+    let ciphertext = from_hex("452c1df04ff289cc7d413b4846c0e86ac4");
+
+    assert_eq!(
+        Some(ciphertext.clone()),
+        aez.encrypt(&nonce, &data, 16, &[0x0c])
+    );
+    assert_eq!(
+        Some(vec![0x0c]),
+        aez.decrypt(&nonce, &data, 16, &ciphertext)
+    );
+
+    let mut tampered = ciphertext;
+    tampered[3] ^= 1;
+    assert_eq!(None, aez.decrypt(&nonce, &data, 16, &tampered));
+}
+
+#[test]
+fn test_short_key_empty_nonce() {
+    // First aezeed vector of LND: a 32 bytes scrypt key, hashed with
+    // BLAKE2b, no nonce and the version and salt as additional data
+    let aez = Aez::new(&from_hex(
+        "73a7eadcffcd7cc7e98c437686b1fef19e4ccfeaed67a89b0ab0cf22e6a59040",
+    ));
+    let data = from_hex("0073616c7431");
+    let ciphertext = from_hex("304b9e6aa12884a7a9ae0abc263fcbe543c58c3cd0890e");
+    let message = from_hex("00000081b637d86359e6960de795e41e0b4cfd");
+
+    assert_eq!(
+        Some(ciphertext.clone()),
+        aez.encrypt(&[], &[&data], 4, &message)
+    );
+    assert_eq!(Some(message), aez.decrypt(&[], &[&data], 4, &ciphertext));
+}
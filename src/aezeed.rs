@@ -0,0 +1,366 @@
+use crate::aez::Aez;
+use crate::error::Error;
+use crate::language::Language;
+use crate::wordlist::Wordlist;
+use std::fmt;
+
+/**
+ * Number of english words of an aezeed phrase
+ */
+pub const WORD_COUNT: usize = 24;
+
+/**
+ * Passphrase used by LND when none is provided
+ */
+pub const DEFAULT_PASSPHRASE: &str = "aezeed";
+
+/**
+ * Only version of the envelope holding the encrypted seed
+ */
+const ENVELOPE_VERSION: u8 = 0;
+
+const CIPHERTEXT_LENGTH: usize = 23;
+const SALT_LENGTH: usize = 5;
+const ENVELOPE_LENGTH: usize = WORD_COUNT * 11 / 8;
+
+/**
+ * Bytes added to the plaintext to authenticate it
+ */
+const EXPANSION: usize = 4;
+
+/**
+ * Days between the unix epoch and the bitcoin genesis block,
+ * from which wallet birthdays are counted
+ */
+const GENESIS_DAYS: i64 = 14247;
+
+/**
+ * Base 2 logarithm of the scrypt cost used by LND, N = 32768.
+ * Its published test vectors are made with N = 16 instead.
+ */
+const SCRYPT_LOG_N: u8 = 15;
+
+/**
+ * Computes the CRC-32C (Castagnoli) checksum of some bytes
+ */
+fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ if crc & 1 == 1 { 0x82F6_3B78 } else { 0 };
+        }
+    }
+    !crc
+}
+
+/**
+ * The encrypted seed held by an aezeed phrase, along with
+ * the salt deriving the key from the passphrase.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    pub version: u8,
+    ciphertext: [u8; CIPHERTEXT_LENGTH],
+    salt: [u8; SALT_LENGTH],
+}
+
+/**
+ * The seed of an LND wallet, decrypted from an aezeed phrase.
+ * Birthday is the number of days between the bitcoin genesis block
+ * and the creation of the wallet.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CipherSeed {
+    pub version: u8,
+    pub birthday: u16,
+    pub entropy: [u8; 16],
+}
+
+/**
+ * Derives the key enciphering a seed from a passphrase and a salt.
+ * LND replaces an empty passphrase by a default one.
+ */
+fn cipher(passphrase: &str, salt: &[u8], log_n: u8) -> Aez {
+    let passphrase = match passphrase {
+        "" => DEFAULT_PASSPHRASE,
+        _ => passphrase,
+    };
+    let params = scrypt::Params::new(log_n, 8, 1, 32).expect("scrypt parameters are valid");
+    let mut key = [0u8; 32];
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key).expect("key length is valid");
+    Aez::new(&key)
+}
+
+/**
+ * The version and salt are authenticated along with the seed
+ */
+fn additional_data(version: u8, salt: &[u8]) -> Vec<u8> {
+    let mut data = vec![version];
+    data.extend_from_slice(salt);
+    data
+}
+
+impl Envelope {
+    /**
+     * Reads the envelope encoded by 24 english words and checks its
+     * version and CRC-32C checksum. Each word encodes 11 bits.
+     */
+    pub fn from_words(words: &[String]) -> Result<Envelope, Error> {
+        if words.len() != WORD_COUNT {
+            return Err(Error::WordCount {
+                expected: WORD_COUNT,
+                found: words.len(),
+            });
+        }
+
+        let mut bytes = [0u8; ENVELOPE_LENGTH];
+        for (position, word) in words.iter().enumerate() {
+            let index = Language::English
+                .index_of(word)
+                .ok_or_else(|| Error::UnknownWord(word.clone()))?;
+            for bit in 0..11 {
+                if (index >> (10 - bit)) & 1 == 1 {
+                    let offset = position * 11 + bit;
+                    bytes[offset / 8] |= 1 << (7 - offset % 8);
+                }
+            }
+        }
+
+        if bytes[0] != ENVELOPE_VERSION {
+            return Err(Error::AezeedVersion(bytes[0]));
+        }
+        let (content, checksum) = bytes.split_at(ENVELOPE_LENGTH - 4);
+        if crc32c(content).to_be_bytes() != checksum {
+            return Err(Error::AezeedChecksum);
+        }
+
+        let mut envelope = Envelope {
+            version: bytes[0],
+            ciphertext: [0; CIPHERTEXT_LENGTH],
+            salt: [0; SALT_LENGTH],
+        };
+        envelope
+            .ciphertext
+            .copy_from_slice(&content[1..1 + CIPHERTEXT_LENGTH]);
+        envelope
+            .salt
+            .copy_from_slice(&content[1 + CIPHERTEXT_LENGTH..]);
+        Ok(envelope)
+    }
+
+    /**
+     * Encodes the envelope as 24 english words, checksum included
+     */
+    pub fn to_words(&self) -> Vec<&'static str> {
+        let mut bytes = vec![self.version];
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&crc32c(&bytes).to_be_bytes());
+
+        (0..WORD_COUNT)
+            .map(|position| {
+                let index = (0..11).fold(0, |index, bit| {
+                    let offset = position * 11 + bit;
+                    (index << 1) | ((bytes[offset / 8] >> (7 - offset % 8)) & 1) as usize
+                });
+                Language::English.words()[index]
+            })
+            .collect()
+    }
+
+    /**
+     * Decrypts the seed with a passphrase, the default one if empty.
+     * The key is derived from the passphrase and salt with scrypt,
+     * the cipher is AEZ with the version and salt as additional data.
+     */
+    pub fn decrypt(&self, passphrase: &str) -> Result<CipherSeed, Error> {
+        self.decrypt_with_cost(passphrase, SCRYPT_LOG_N)
+    }
+
+    fn decrypt_with_cost(&self, passphrase: &str, log_n: u8) -> Result<CipherSeed, Error> {
+        let data = additional_data(self.version, &self.salt);
+        let plaintext = cipher(passphrase, &self.salt, log_n)
+            .decrypt(&[], &[&data], EXPANSION, &self.ciphertext)
+            .ok_or(Error::AezeedDecryption)?;
+
+        let mut entropy = [0u8; 16];
+        entropy.copy_from_slice(&plaintext[3..]);
+        Ok(CipherSeed {
+            version: plaintext[0],
+            birthday: u16::from_be_bytes([plaintext[1], plaintext[2]]),
+            entropy,
+        })
+    }
+}
+
+impl CipherSeed {
+    /**
+     * Encrypts the seed with a passphrase and a salt
+     */
+    pub fn encrypt(&self, passphrase: &str, salt: [u8; SALT_LENGTH]) -> Envelope {
+        let mut plaintext = vec![self.version];
+        plaintext.extend_from_slice(&self.birthday.to_be_bytes());
+        plaintext.extend_from_slice(&self.entropy);
+
+        let data = additional_data(ENVELOPE_VERSION, &salt);
+        let encrypted = cipher(passphrase, &salt, SCRYPT_LOG_N)
+            .encrypt(&[], &[&data], EXPANSION, &plaintext)
+            .expect("seed is short enough");
+        let mut ciphertext = [0u8; CIPHERTEXT_LENGTH];
+        ciphertext.copy_from_slice(&encrypted);
+        Envelope {
+            version: ENVELOPE_VERSION,
+            ciphertext,
+            salt,
+        }
+    }
+
+    /**
+     * Returns the day the wallet was created
+     */
+    pub fn birthday_date(&self) -> Date {
+        Date::from_epoch_days(GENESIS_DAYS + self.birthday as i64)
+    }
+}
+
+/**
+ * A day of the gregorian calendar
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /**
+     * Converts a number of days since the unix epoch to a date
+     */
+    pub fn from_epoch_days(days: i64) -> Date {
+        // Counts from 0000-03-01 so leap days end each 4 years cycle
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+fn words(phrase: &str) -> Vec<String> {
+    phrase.split(' ').map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn test_crc32c() {
+    assert_eq!(0xE306_9283, crc32c(b"123456789"));
+}
+
+#[test]
+fn test_date() {
+    assert_eq!("1970-01-01", Date::from_epoch_days(0).to_string());
+    assert_eq!(
+        "2009-01-03",
+        Date::from_epoch_days(GENESIS_DAYS).to_string()
+    );
+    assert_eq!("2000-02-29", Date::from_epoch_days(11016).to_string());
+}
+
+#[test]
+fn test_envelope() {
+    // Test vector of LND, with the salt "salt1"
+    let phrase = "able tree stool crush transfer cloud cross three profit outside hen \
+                  citizen plate ride require leg siren drum success suggest drink require \
+                  fiscal upgrade";
+    let envelope = Envelope::from_words(&words(phrase)).unwrap();
+    assert_eq!(0, envelope.version);
+    assert_eq!(*b"salt1", envelope.salt);
+    assert_eq!(words(phrase), envelope.to_words());
+
+    // Changing a word breaks the checksum
+    let corrupted = phrase.replace("stool", "stock");
+    assert_eq!(
+        Err(Error::AezeedChecksum),
+        Envelope::from_words(&words(&corrupted))
+    );
+
+    // A valid BIP-39 phrase is not an envelope
+    let bip39 = "erupt quit sphere taxi air decade vote mixed life elevator mammal search \
+                 empower rabbit barely indoor crush grid slide correct scatter deal tenant verb";
+    assert!(Envelope::from_words(&words(bip39)).is_err());
+}
+
+#[test]
+fn test_lnd_vectors() {
+    let entropy = [
+        0x81, 0xb6, 0x37, 0xd8, 0x63, 0x59, 0xe6, 0x96, 0x0d, 0xe7, 0x95, 0xe4, 0x1e, 0x0b, 0x4c,
+        0xfd,
+    ];
+
+    // Without passphrase, LND uses the default one
+    let phrase = "ability liquid travel stem barely drastic pact cupboard apple thrive morning \
+                  oak feature tissue couch old math inform success suggest drink motion know \
+                  royal";
+    let envelope = Envelope::from_words(&words(phrase)).unwrap();
+    let seed = envelope.decrypt_with_cost("", 4).unwrap();
+    assert_eq!(0, seed.version);
+    assert_eq!(0, seed.birthday);
+    assert_eq!(entropy, seed.entropy);
+    assert_eq!(Ok(seed), envelope.decrypt_with_cost(DEFAULT_PASSPHRASE, 4));
+
+    let phrase = "able tree stool crush transfer cloud cross three profit outside hen \
+                  citizen plate ride require leg siren drum success suggest drink require \
+                  fiscal upgrade";
+    let envelope = Envelope::from_words(&words(phrase)).unwrap();
+    let seed = envelope
+        .decrypt_with_cost("!very_safe_55345_password*", 4)
+        .unwrap();
+    assert_eq!(0, seed.version);
+    assert_eq!(3365, seed.birthday);
+    assert_eq!(entropy, seed.entropy);
+    assert_eq!(
+        Err(Error::AezeedDecryption),
+        envelope.decrypt_with_cost("", 4)
+    );
+}
+
+#[test]
+fn test_decrypt() {
+    let seed = CipherSeed {
+        version: 0,
+        birthday: 3365,
+        entropy: [
+            0x81, 0xb6, 0x37, 0xd8, 0x63, 0x59, 0xe6, 0x96, 0x0d, 0xe7, 0x95, 0xe4, 0x1e, 0x0b,
+            0x4c, 0xfd,
+        ],
+    };
+    assert_eq!("2018-03-22", seed.birthday_date().to_string());
+
+    let words: Vec<String> = seed
+        .encrypt("passphrase", *b"salt1")
+        .to_words()
+        .iter()
+        .map(|word| word.to_string())
+        .collect();
+    let envelope = Envelope::from_words(&words).unwrap();
+    assert_eq!(Ok(seed), envelope.decrypt("passphrase"));
+    assert_eq!(Err(Error::AezeedDecryption), envelope.decrypt(""));
+}
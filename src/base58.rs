@@ -0,0 +1,117 @@
+use sha2::{Digest, Sha256};
+
+/**
+ * Characters of the bitcoin base58 alphabet, in the order of their values
+ */
+const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/**
+ * Returns the 4 bytes checksum of base58check : the first bytes
+ * of the double SHA-256 of the payload
+ */
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/**
+ * Encodes bytes in base58, each leading zero byte as a '1'
+ */
+pub fn encode(bytes: &[u8]) -> String {
+    // Little-endian base58 digits of the number, grown as bytes are added
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|digit| ALPHABET[*digit as usize] as char),
+        )
+        .collect()
+}
+
+/**
+ * Decodes a base58 string, returns None if a character is not
+ * part of the alphabet
+ */
+pub fn decode(string: &str) -> Option<Vec<u8>> {
+    // Little-endian bytes of the number
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in string.bytes() {
+        let mut carry = ALPHABET.iter().position(|letter| *letter == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let zeros = string.bytes().take_while(|c| *c == b'1').count();
+    Some(
+        std::iter::repeat_n(0, zeros)
+            .chain(bytes.into_iter().rev())
+            .collect(),
+    )
+}
+
+/**
+ * Encodes a payload followed by its checksum in base58
+ */
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut bytes = payload.to_vec();
+    bytes.extend_from_slice(&checksum(payload));
+    encode(&bytes)
+}
+
+/**
+ * Decodes a base58 string and checks the checksum ending it,
+ * returns the payload without the checksum
+ */
+pub fn decode_check(string: &str) -> Option<Vec<u8>> {
+    let mut bytes = decode(string)?;
+    if bytes.len() < 4 {
+        return None;
+    }
+    let expected = bytes.split_off(bytes.len() - 4);
+    match checksum(&bytes)[..] == expected[..] {
+        true => Some(bytes),
+        false => None,
+    }
+}
+
+#[test]
+fn test_base58() {
+    assert_eq!("", encode(&[]));
+    assert_eq!("11", encode(&[0, 0]));
+    assert_eq!("5Q", encode(&[0xFF]));
+    assert_eq!("11StV1DL6CwTryKyV", encode(b"\0\0hello world"));
+    assert_eq!(
+        Some(b"\0\0hello world".to_vec()),
+        decode("11StV1DL6CwTryKyV")
+    );
+    assert_eq!(None, decode("0OIl"));
+
+    let encoded = encode_check(b"b39wc");
+    assert_eq!(Some(b"b39wc".to_vec()), decode_check(&encoded));
+    let mut corrupted = encoded.into_bytes();
+    corrupted[2] = if corrupted[2] == b'2' { b'3' } else { b'2' };
+    assert_eq!(None, decode_check(&String::from_utf8(corrupted).unwrap()));
+}
@@ -0,0 +1,387 @@
+use crate::base58;
+use crate::error::Error;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+
+/**
+ * Offset of hardened child indexes
+ */
+pub const HARDENED: u32 = 0x8000_0000;
+
+/**
+ * Version bytes of mainnet extended private keys (xprv)
+ */
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+
+/**
+ * Version bytes of mainnet extended public keys, with the script
+ * type their prefix stands for : xpub, ypub and zpub
+ */
+const XPUB_VERSIONS: [([u8; 4], ScriptType); 3] = [
+    ([0x04, 0x88, 0xB2, 0x1E], ScriptType::Legacy),
+    ([0x04, 0x9D, 0x7C, 0xB2], ScriptType::NestedSegwit),
+    ([0x04, 0xB2, 0x47, 0x46], ScriptType::NativeSegwit),
+];
+
+/**
+ * Order of the secp256k1 curve, big-endian
+ */
+const CURVE_ORDER: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+    0xBA, 0xAE, 0xDC, 0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x41,
+];
+
+/**
+ * Checks that a private key is between 1 and the curve order excluded
+ */
+fn is_valid_key(key: &[u8; 32]) -> bool {
+    key.iter().any(|byte| *byte != 0) && key[..] < CURVE_ORDER[..]
+}
+
+/**
+ * Adds two private keys modulo the curve order
+ */
+fn add_keys(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut sum = [0u8; 32];
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let total = a[i] as u16 + b[i] as u16 + carry;
+        sum[i] = total as u8;
+        carry = total >> 8;
+    }
+
+    // Both keys are below the order, so subtracting it once is enough
+    if carry == 1 || sum[..] >= CURVE_ORDER[..] {
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let difference = sum[i] as i16 - CURVE_ORDER[i] as i16 - borrow;
+            sum[i] = difference.rem_euclid(256) as u8;
+            borrow = if difference < 0 { 1 } else { 0 };
+        }
+    }
+    sum
+}
+
+/**
+ * Computes the RIPEMD-160 of the SHA-256 of some bytes, which
+ * identifies public keys in addresses and fingerprints
+ */
+pub fn hash160(bytes: &[u8]) -> [u8; 20] {
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&Ripemd160::digest(Sha256::digest(bytes)));
+    hash
+}
+
+/**
+ * Reads a derivation path such as `m/84'/0'/0'`. Hardened indexes
+ * are marked with an apostrophe or an h.
+ */
+pub fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    let invalid = || Error::InvalidDerivationPath(path.to_string());
+    let mut components = path.trim().split('/');
+    if components.next() != Some("m") {
+        return Err(invalid());
+    }
+    components
+        .map(|component| {
+            let (number, offset) = match component.strip_suffix(|c| c == '\'' || c == 'h') {
+                Some(number) => (number, HARDENED),
+                None => (component, 0),
+            };
+            match number.parse::<u32>() {
+                Ok(index) if index < HARDENED => Ok(index | offset),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/**
+ * Script types of single key accounts, each with its own BIP-44
+ * purpose and extended public key prefix
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    Legacy,
+    NestedSegwit,
+    NativeSegwit,
+}
+
+impl ScriptType {
+    /**
+     * Returns the path of the first account : m/purpose'/0'/0'
+     */
+    pub fn account_path(self) -> Vec<u32> {
+        let purpose = match self {
+            ScriptType::Legacy => 44,
+            ScriptType::NestedSegwit => 49,
+            ScriptType::NativeSegwit => 84,
+        };
+        vec![purpose | HARDENED, HARDENED, HARDENED]
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("any key length is valid");
+    for part in data {
+        mac.update(part);
+    }
+    let result = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+    (left, right)
+}
+
+/**
+ * A BIP-32 extended private key : a private key and its chain code
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedKey {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    /**
+     * Derives the master key of a seed
+     */
+    pub fn from_seed(seed: &[u8]) -> Result<ExtendedKey, Error> {
+        let (key, chain_code) = hmac_sha512(b"Bitcoin seed", &[seed]);
+        if !is_valid_key(&key) {
+            return Err(Error::InvalidDerivation);
+        }
+        Ok(ExtendedKey { key, chain_code })
+    }
+
+    /**
+     * Reads a mainnet extended private key (xprv)
+     */
+    pub fn from_xprv(xprv: &str) -> Result<ExtendedKey, Error> {
+        let bytes = base58::decode_check(xprv.trim()).ok_or(Error::InvalidExtendedKey)?;
+        if bytes.len() != 78 || bytes[..4] != XPRV_VERSION || bytes[45] != 0 {
+            return Err(Error::InvalidExtendedKey);
+        }
+        let mut key = ExtendedKey {
+            key: [0; 32],
+            chain_code: [0; 32],
+        };
+        key.chain_code.copy_from_slice(&bytes[13..45]);
+        key.key.copy_from_slice(&bytes[46..]);
+        if !is_valid_key(&key.key) {
+            return Err(Error::InvalidExtendedKey);
+        }
+        Ok(key)
+    }
+
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /**
+     * Returns the compressed public key of the private key
+     */
+    pub fn public_key(&self) -> [u8; 33] {
+        let point = k256::SecretKey::from_slice(&self.key)
+            .expect("keys are checked when derived")
+            .public_key()
+            .to_encoded_point(true);
+        let mut key = [0u8; 33];
+        key.copy_from_slice(point.as_bytes());
+        key
+    }
+
+    /**
+     * Returns the fingerprint of the key : the first bytes of the
+     * hash of its public key
+     */
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = hash160(&self.public_key());
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    /**
+     * Derives the child of an index, hardened if the index is greater
+     * than the hardened offset, normal otherwise
+     */
+    pub fn derive(&self, index: u32) -> Result<ExtendedKey, Error> {
+        if index >= HARDENED {
+            return self.derive_hardened(index - HARDENED);
+        }
+        let (tweak, chain_code) = hmac_sha512(
+            &self.chain_code,
+            &[&self.public_key(), &index.to_be_bytes()],
+        );
+        self.child(tweak, chain_code)
+    }
+
+    /**
+     * Derives the children of a path, one index after the other
+     */
+    pub fn derive_path(&self, path: &[u32]) -> Result<ExtendedKey, Error> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive(*index))
+    }
+
+    fn child(&self, tweak: [u8; 32], chain_code: [u8; 32]) -> Result<ExtendedKey, Error> {
+        if !is_valid_key(&tweak) {
+            return Err(Error::InvalidDerivation);
+        }
+        let key = add_keys(&self.key, &tweak);
+        if !is_valid_key(&key) {
+            return Err(Error::InvalidDerivation);
+        }
+        Ok(ExtendedKey { key, chain_code })
+    }
+
+    /**
+     * Derives the hardened child of an index, below the hardened offset
+     */
+    pub fn derive_hardened(&self, index: u32) -> Result<ExtendedKey, Error> {
+        let index = (index | HARDENED).to_be_bytes();
+        let (tweak, chain_code) = hmac_sha512(&self.chain_code, &[&[0], &self.key, &index]);
+        self.child(tweak, chain_code)
+    }
+
+    /**
+     * Derives the hardened children of a path, one index after the other
+     */
+    pub fn derive_hardened_path(&self, path: &[u32]) -> Result<ExtendedKey, Error> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_hardened(*index))
+    }
+}
+
+/**
+ * A BIP-32 extended public key, with the depth of its derivation
+ * and the script type of its prefix
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    pub depth: u8,
+    pub script_type: ScriptType,
+    pub key: [u8; 33],
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedPublicKey {
+    /**
+     * Reads a mainnet extended public key (xpub, ypub or zpub)
+     */
+    pub fn from_xpub(xpub: &str) -> Result<ExtendedPublicKey, Error> {
+        let bytes = base58::decode_check(xpub.trim()).ok_or(Error::InvalidExtendedPublicKey)?;
+        if bytes.len() != 78 || (bytes[45] != 2 && bytes[45] != 3) {
+            return Err(Error::InvalidExtendedPublicKey);
+        }
+        let script_type = XPUB_VERSIONS
+            .iter()
+            .find(|(version, _)| bytes[..4] == version[..])
+            .map(|(_, script_type)| *script_type)
+            .ok_or(Error::InvalidExtendedPublicKey)?;
+
+        let mut key = ExtendedPublicKey {
+            depth: bytes[4],
+            script_type,
+            key: [0; 33],
+            chain_code: [0; 32],
+        };
+        key.chain_code.copy_from_slice(&bytes[13..45]);
+        key.key.copy_from_slice(&bytes[45..]);
+        Ok(key)
+    }
+
+    /**
+     * Checks that a private key is the one of this public key
+     */
+    pub fn matches(&self, private: &ExtendedKey) -> bool {
+        private.chain_code == self.chain_code && private.public_key() == self.key
+    }
+}
+
+#[test]
+fn test_derivation() {
+    use crate::encoding::{from_hex, to_hex};
+
+    // Test vector 1 of BIP-32
+    let master =
+        ExtendedKey::from_seed(&from_hex("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+    assert_eq!(
+        "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        to_hex(master.private_key())
+    );
+    assert_eq!(
+        "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+        to_hex(master.chain_code())
+    );
+
+    let child = master.derive_hardened(0).unwrap();
+    assert_eq!(
+        "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        to_hex(child.private_key())
+    );
+    assert_eq!(
+        "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+        to_hex(child.chain_code())
+    );
+    assert_eq!(Ok(child), master.derive_hardened_path(&[0]));
+}
+
+#[test]
+fn test_add_keys() {
+    let mut max = CURVE_ORDER;
+    max[31] -= 1;
+    let mut two = [0u8; 32];
+    two[31] = 2;
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    assert_eq!(one, add_keys(&max, &two));
+    assert!(!is_valid_key(&CURVE_ORDER));
+    assert!(!is_valid_key(&[0; 32]));
+}
+
+#[test]
+fn test_public_derivation() {
+    use crate::encoding::{from_hex, to_hex};
+
+    // Test vector 1 of BIP-32
+    let master =
+        ExtendedKey::from_seed(&from_hex("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+    assert_eq!(
+        "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+        to_hex(&master.public_key())
+    );
+    assert_eq!([0x34, 0x42, 0x19, 0x3e], master.fingerprint());
+
+    let path = parse_path("m/0h/1/2'").unwrap();
+    assert_eq!(vec![HARDENED, 1, 2 | HARDENED], path);
+    let child = master.derive_path(&path).unwrap();
+    assert_eq!(
+        "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        to_hex(child.private_key())
+    );
+
+    let xpub = ExtendedPublicKey::from_xpub(
+        "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+    )
+    .unwrap();
+    assert_eq!(3, xpub.depth);
+    assert_eq!(ScriptType::Legacy, xpub.script_type);
+    assert!(xpub.matches(&child));
+    assert!(!xpub.matches(&master));
+
+    assert_eq!(
+        Err(Error::InvalidDerivationPath("m/0/x".to_string())),
+        parse_path("m/0/x")
+    );
+    assert_eq!(
+        Err(Error::InvalidDerivationPath("0/1".to_string())),
+        parse_path("0/1")
+    );
+}
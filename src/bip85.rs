@@ -0,0 +1,263 @@
+use crate::base58;
+use crate::bip32::ExtendedKey;
+use crate::error::Error;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+/**
+ * Purpose of the BIP-85 derivation paths, "BIPE" as digits
+ */
+pub const PURPOSE: u32 = 83_696_968;
+
+const BIP39_APPLICATION: u32 = 39;
+const WIF_APPLICATION: u32 = 2;
+const HEX_APPLICATION: u32 = 128_169;
+const BASE64_APPLICATION: u32 = 707_764;
+const BASE85_APPLICATION: u32 = 707_785;
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/**
+ * Alphabet of base85 as defined by RFC 1924
+ */
+const BASE85_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/**
+ * Returns the code of a language in BIP-85 derivation paths.
+ * Portuguese was added to BIP-39 after BIP-85 and has no code.
+ */
+pub fn language_code(language: Language) -> Result<u32, Error> {
+    match language {
+        Language::English => Ok(0),
+        Language::Japanese => Ok(1),
+        Language::Korean => Ok(2),
+        Language::Spanish => Ok(3),
+        Language::ChineseSimplified => Ok(4),
+        Language::ChineseTraditional => Ok(5),
+        Language::French => Ok(6),
+        Language::Italian => Ok(7),
+        Language::Czech => Ok(8),
+        Language::Portuguese => Err(Error::Bip85Language(language.to_string())),
+    }
+}
+
+/**
+ * Derives the 64 bytes of entropy of a hardened path under the BIP-85
+ * purpose : the HMAC-SHA512 of the derived private key
+ */
+pub fn derive_entropy(master: &ExtendedKey, path: &[u32]) -> Result<[u8; 64], Error> {
+    let full_path: Vec<u32> = std::iter::once(PURPOSE)
+        .chain(path.iter().copied())
+        .collect();
+    let key = master.derive_hardened_path(&full_path)?;
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(b"bip-entropy-from-k").expect("any key length is valid");
+    mac.update(key.private_key());
+    let mut entropy = [0u8; 64];
+    entropy.copy_from_slice(&mac.finalize().into_bytes());
+    Ok(entropy)
+}
+
+fn check_length(application: &str, length: usize, min: usize, max: usize) -> Result<(), Error> {
+    if length < min || length > max {
+        return Err(Error::Bip85Length {
+            application: application.to_string(),
+            length,
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
+/**
+ * Derives a child phrase of some words in a language
+ */
+pub fn bip39(
+    master: &ExtendedKey,
+    language: Language,
+    words: usize,
+    index: u32,
+) -> Result<Mnemonic, Error> {
+    if !Mnemonic::VALID_LENGTHS.contains(&words) {
+        return Err(Error::ChecksumLength(words));
+    }
+    let path = [
+        BIP39_APPLICATION,
+        language_code(language)?,
+        words as u32,
+        index,
+    ];
+    let entropy = derive_entropy(master, &path)?;
+    Mnemonic::from_entropy(&entropy[..words * 4 / 3], &language)
+}
+
+/**
+ * Derives a private key, as a compressed mainnet WIF key
+ */
+pub fn wif(master: &ExtendedKey, index: u32) -> Result<String, Error> {
+    let entropy = derive_entropy(master, &[WIF_APPLICATION, index])?;
+    let mut payload = vec![0x80];
+    payload.extend_from_slice(&entropy[..32]);
+    payload.push(0x01);
+    Ok(base58::encode_check(&payload))
+}
+
+/**
+ * Derives 16 to 64 bytes of entropy
+ */
+pub fn hex(master: &ExtendedKey, bytes: usize, index: u32) -> Result<Vec<u8>, Error> {
+    check_length("HEX", bytes, 16, 64)?;
+    let entropy = derive_entropy(master, &[HEX_APPLICATION, bytes as u32, index])?;
+    Ok(entropy[..bytes].to_vec())
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut string = String::new();
+    for chunk in bytes.chunks(3) {
+        let value = chunk
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(3)
+            .fold(0u32, |value, byte| (value << 8) | *byte as u32);
+        for position in 0..4 {
+            string.push(match position <= chunk.len() {
+                true => BASE64_ALPHABET[(value >> (18 - 6 * position)) as usize & 0x3F] as char,
+                false => '=',
+            });
+        }
+    }
+    string
+}
+
+/**
+ * Encodes bytes in base85, 4 bytes as 5 characters, the padding
+ * of the last group being removed
+ */
+fn encode_base85(bytes: &[u8]) -> String {
+    let mut string = String::new();
+    for chunk in bytes.chunks(4) {
+        let value = chunk
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(4)
+            .fold(0u32, |value, byte| (value << 8) | *byte as u32);
+        let digits: Vec<char> = (0..5)
+            .rev()
+            .map(|position| BASE85_ALPHABET[(value / 85u32.pow(position)) as usize % 85] as char)
+            .collect();
+        string.extend(&digits[..chunk.len() + 1]);
+    }
+    string
+}
+
+/**
+ * Derives a password of 20 to 86 base64 characters
+ */
+pub fn base64_password(master: &ExtendedKey, length: usize, index: u32) -> Result<String, Error> {
+    check_length("base64 passwords", length, 20, 86)?;
+    let entropy = derive_entropy(master, &[BASE64_APPLICATION, length as u32, index])?;
+    Ok(encode_base64(&entropy)[..length].to_string())
+}
+
+/**
+ * Derives a password of 10 to 80 base85 characters
+ */
+pub fn base85_password(master: &ExtendedKey, length: usize, index: u32) -> Result<String, Error> {
+    check_length("base85 passwords", length, 10, 80)?;
+    let entropy = derive_entropy(master, &[BASE85_APPLICATION, length as u32, index])?;
+    Ok(encode_base85(&entropy)[..length].to_string())
+}
+
+#[cfg(test)]
+fn test_master() -> ExtendedKey {
+    ExtendedKey::from_xprv(
+        "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_encodings() {
+    assert_eq!("Zm9vYg==", encode_base64(b"foob"));
+    assert_eq!("Zm9vYmE=", encode_base64(b"fooba"));
+    assert_eq!("Zm9vYmFy", encode_base64(b"foobar"));
+    // base64.b85encode of Python
+    assert_eq!("W^Zp|VR8", encode_base85(b"foobar"));
+}
+
+#[test]
+fn test_vectors() {
+    use crate::encoding::to_hex;
+
+    // Test vectors of BIP-85
+    let master = test_master();
+    assert_eq!(
+        "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7",
+        to_hex(&derive_entropy(&master, &[0, 0]).unwrap())
+    );
+
+    let phrase = |words| {
+        bip39(&master, Language::English, words, 0)
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+        phrase(12)
+    );
+    assert_eq!(
+        "near account window bike charge season chef number sketch tomorrow excuse sniff \
+         circle vital hockey outdoor supply token",
+        phrase(18)
+    );
+    assert_eq!(
+        "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget \
+         divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+        phrase(24)
+    );
+
+    assert_eq!(
+        Ok("Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp".to_string()),
+        wif(&master, 0)
+    );
+    assert_eq!(
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c",
+        to_hex(&hex(&master, 64, 0).unwrap())
+    );
+    assert_eq!(
+        Ok("dKLoepugzdVJvdL56ogNV".to_string()),
+        base64_password(&master, 21, 0)
+    );
+    assert_eq!(
+        Ok("_s`{TW89)i4`".to_string()),
+        base85_password(&master, 12, 0)
+    );
+}
+
+#[test]
+fn test_lengths() {
+    let master = test_master();
+    assert_eq!(
+        Err(Error::ChecksumLength(13)),
+        bip39(&master, Language::English, 13, 0)
+    );
+    assert_eq!(
+        Err(Error::Bip85Language("portuguese".to_string())),
+        bip39(&master, Language::Portuguese, 12, 0)
+    );
+    assert_eq!(
+        Err(Error::Bip85Length {
+            application: "HEX".to_string(),
+            length: 65,
+            min: 16,
+            max: 64
+        }),
+        hex(&master, 65, 0)
+    );
+    assert!(base85_password(&master, 81, 0).is_err());
+    assert!(base64_password(&master, 19, 0).is_err());
+}
@@ -0,0 +1,190 @@
+use crate::error::Error;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use crate::suggest::distance;
+use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * Rules a word from the corpus must follow to be selected
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildRules {
+    /// Number of words to select
+    pub size: usize,
+    /// Minimum number of characters of a word
+    pub min_length: usize,
+    /// Maximum number of characters of a word
+    pub max_length: usize,
+    /// Number of leading characters that must be unique to each word
+    pub prefix_length: usize,
+    /// Minimum edit distance between two selected words
+    pub min_distance: usize,
+    /// Whether words of the built-in dictionaries are refused
+    pub exclude_builtin: bool,
+}
+
+impl Default for BuildRules {
+    fn default() -> BuildRules {
+        BuildRules {
+            size: Mnemonic::WORDLIST_SIZE,
+            min_length: 3,
+            max_length: 8,
+            prefix_length: 4,
+            min_distance: 2,
+            exclude_builtin: true,
+        }
+    }
+}
+
+/**
+ * Splits a corpus into lowercase words, in their order of first appearance.
+ * Corpus is expected to list the most common words first.
+ */
+fn corpus_words(corpus: &str) -> Vec<String> {
+    let composed: String = corpus.nfc().collect();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut words: Vec<String> = Vec::new();
+
+    for word in composed.split(|c: char| !c.is_alphabetic()) {
+        let word = word.to_lowercase();
+        if !word.is_empty() && seen.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+
+    words
+}
+
+/**
+ * Selects the words of a new dictionary from a corpus.
+ * Words are picked in corpus order as long as they follow the rules,
+ * then returned NFKD normalized and sorted.
+ */
+pub fn build_wordlist(corpus: &str, rules: &BuildRules) -> Result<Vec<String>, Error> {
+    let builtin: HashSet<String> = if rules.exclude_builtin {
+        Language::ALL
+            .iter()
+            .flat_map(|language| language.words().iter())
+            .map(|word| word.nfc().collect())
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let mut selected: Vec<String> = Vec::new();
+    let mut prefixes: HashSet<String> = HashSet::new();
+
+    for word in corpus_words(corpus) {
+        if selected.len() == rules.size {
+            break;
+        }
+
+        let length = word.chars().count();
+        if length < rules.min_length || length > rules.max_length || builtin.contains(&word) {
+            continue;
+        }
+
+        let prefix: String = word.chars().take(rules.prefix_length).collect();
+        if prefixes.contains(&prefix) {
+            continue;
+        }
+
+        let too_close = selected.iter().any(|other| {
+            let difference = (other.chars().count() as isize - length as isize).unsigned_abs();
+            difference < rules.min_distance && distance(&word, other) < rules.min_distance
+        });
+        if too_close {
+            continue;
+        }
+
+        prefixes.insert(prefix);
+        selected.push(word);
+    }
+
+    if selected.len() < rules.size {
+        return Err(Error::CorpusTooSmall {
+            found: selected.len(),
+            expected: rules.size,
+        });
+    }
+
+    let mut words: Vec<String> = selected.iter().map(|word| word.nfkd().collect()).collect();
+    words.sort();
+    Ok(words)
+}
+
+/**
+ * Formats a dictionary as a plain text file, one word per line
+ */
+pub fn to_text(words: &[String]) -> String {
+    let mut text = words.join("\n");
+    text.push('\n');
+    text
+}
+
+/**
+ * Formats a dictionary as a Rust source file,
+ * in the format used by the built-in dictionaries
+ */
+pub fn to_rust(name: &str, words: &[String]) -> String {
+    let mut source = format!(
+        "pub static {}_WORDS: [&str; {}] = [\n",
+        name.to_uppercase(),
+        words.len()
+    );
+    for word in words {
+        source.push_str(&format!("    \"{}\",\n", word));
+    }
+    source.push_str("];\n");
+    source
+}
+
+#[cfg(test)]
+fn test_rules() -> BuildRules {
+    BuildRules {
+        size: 4,
+        ..BuildRules::default()
+    }
+}
+
+#[test]
+fn test_build_wordlist() {
+    let corpus = "Xy zorglub, zorglubs ! Abandon krakou bliptonic. Vélozo quarx krakou";
+    let words = build_wordlist(corpus, &test_rules()).unwrap();
+
+    // "xy" is too short, "zorglubs" shares the "zorg" prefix, "abandon"
+    // is a built-in word and "bliptonic" is too long
+    assert_eq!(vec!["krakou", "quarx", "ve\u{301}lozo", "zorglub"], words);
+}
+
+#[test]
+fn test_build_wordlist_distance() {
+    let rules = BuildRules {
+        size: 3,
+        min_distance: 3,
+        prefix_length: 1,
+        exclude_builtin: false,
+        ..BuildRules::default()
+    };
+    let words = build_wordlist("cat bat bag dog emu", &rules).unwrap();
+    assert_eq!(vec!["cat", "dog", "emu"], words);
+
+    assert_eq!(
+        Err(Error::CorpusTooSmall {
+            found: 1,
+            expected: 3
+        }),
+        build_wordlist("cat bat", &rules)
+    );
+}
+
+#[test]
+fn test_to_rust() {
+    let words = vec!["chat".to_string(), "chien".to_string()];
+    assert_eq!("chat\nchien\n", to_text(&words));
+    assert_eq!(
+        "pub static BRETON_WORDS: [&str; 2] = [\n    \"chat\",\n    \"chien\",\n];\n",
+        to_rust("breton", &words)
+    );
+}
@@ -0,0 +1,192 @@
+use crate::encoding::crc32;
+use crate::error::Error;
+
+/**
+ * The 256 Bytewords of Blockchain Commons, one for each byte value.
+ * Their first and last letters are unique and make the minimal style.
+ */
+#[rustfmt::skip]
+const WORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt",
+    "away", "axis", "back", "bald", "barn", "belt", "beta", "bias",
+    "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost",
+    "crux", "curl", "cusp", "cyan", "dark", "data", "days", "deli",
+    "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam",
+    "exit", "eyes", "fact", "fair", "fern", "figs", "film", "fish",
+    "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow",
+    "good", "gray", "grim", "guru", "gush", "gyro", "half", "hang",
+    "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into",
+    "iris", "iron", "item", "jade", "jazz", "join", "jolt", "jowl",
+    "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb",
+    "lava", "lazy", "leaf", "legs", "liar", "limp", "lion", "list",
+    "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss",
+    "monk", "nail", "navy", "need", "news", "next", "noon", "note",
+    "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose",
+    "puff", "puma", "purr", "quad", "quiz", "race", "ramp", "real",
+    "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot",
+    "soap", "solo", "song", "stub", "surf", "swan", "taco", "task",
+    "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user",
+    "vast", "very", "veto", "vial", "vibe", "view", "visa", "void",
+    "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell",
+    "yoga", "yurt", "zaps", "zero", "zest", "zinc", "zone", "zoom",
+];
+
+/**
+ * Ways of writing Bytewords : whole words separated by spaces or
+ * dashes, or only the first and last letters of each word, as in URs
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Standard,
+    Uri,
+    Minimal,
+}
+
+/**
+ * Returns the two letters of a word in the minimal style
+ */
+fn minimal(word: &str) -> String {
+    let bytes = word.as_bytes();
+    format!("{}{}", bytes[0] as char, bytes[3] as char)
+}
+
+/**
+ * Encodes bytes as Bytewords, followed by the big-endian CRC-32 of the bytes
+ */
+pub fn encode(data: &[u8], style: Style) -> String {
+    let checksum = crc32(data).to_be_bytes();
+    let words = data.iter().chain(checksum.iter()).map(|byte| {
+        let word = WORDS[*byte as usize];
+        match style {
+            Style::Minimal => minimal(word),
+            _ => word.to_string(),
+        }
+    });
+    match style {
+        Style::Standard => words.collect::<Vec<String>>().join(" "),
+        Style::Uri => words.collect::<Vec<String>>().join("-"),
+        Style::Minimal => words.collect(),
+    }
+}
+
+/**
+ * Returns the byte encoded by a word, whole or minimal
+ */
+fn decode_word(word: &str) -> Result<u8, Error> {
+    let lowercase = word.to_lowercase();
+    WORDS
+        .iter()
+        .position(|candidate| match lowercase.len() {
+            2 => minimal(candidate) == lowercase,
+            _ => *candidate == lowercase,
+        })
+        .map(|index| index as u8)
+        .ok_or_else(|| Error::UnknownByteword(word.to_string()))
+}
+
+/**
+ * Decodes Bytewords into bytes, checking and removing the CRC-32
+ * held by the 4 last words
+ */
+pub fn decode(encoded: &str, style: Style) -> Result<Vec<u8>, Error> {
+    let words: Vec<&str> = match style {
+        Style::Standard => encoded.split_whitespace().collect(),
+        Style::Uri => encoded.split('-').collect(),
+        Style::Minimal => {
+            if !encoded.is_ascii() || !encoded.len().is_multiple_of(2) {
+                return Err(Error::UnknownByteword(encoded.to_string()));
+            }
+            (0..encoded.len())
+                .step_by(2)
+                .map(|start| &encoded[start..start + 2])
+                .collect()
+        }
+    };
+    if words.len() < 4 {
+        return Err(Error::InvalidBytewordsChecksum);
+    }
+    let bytes = words
+        .iter()
+        .map(|word| decode_word(word))
+        .collect::<Result<Vec<u8>, Error>>()?;
+
+    let (data, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(data).to_be_bytes() != checksum {
+        return Err(Error::InvalidBytewordsChecksum);
+    }
+    Ok(data.to_vec())
+}
+
+#[test]
+fn test_bytewords() {
+    let input = [0, 1, 2, 128, 255];
+    assert_eq!(
+        "able acid also lava zoom jade need echo taxi",
+        encode(&input, Style::Standard)
+    );
+    assert_eq!(
+        "able-acid-also-lava-zoom-jade-need-echo-taxi",
+        encode(&input, Style::Uri)
+    );
+    assert_eq!("aeadaolazmjendeoti", encode(&input, Style::Minimal));
+
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode(
+            "able acid also lava zoom jade need echo taxi",
+            Style::Standard
+        )
+    );
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode("able-acid-also-lava-zoom-jade-need-echo-taxi", Style::Uri)
+    );
+    assert_eq!(
+        Ok(input.to_vec()),
+        decode("AEADAOLAZMJENDEOTI", Style::Minimal)
+    );
+    assert_eq!(
+        Ok(vec![]),
+        decode(&encode(&[], Style::Minimal), Style::Minimal)
+    );
+}
+
+#[test]
+fn test_invalid_bytewords() {
+    assert_eq!(
+        Err(Error::InvalidBytewordsChecksum),
+        decode(
+            "able acid also lava zero jade need echo wolf",
+            Style::Standard
+        )
+    );
+    assert_eq!(
+        Err(Error::InvalidBytewordsChecksum),
+        decode("aeadaolazojendeowf", Style::Minimal)
+    );
+    assert_eq!(
+        Err(Error::UnknownByteword("abel".to_string())),
+        decode(
+            "abel acid also lava zoom jade need echo taxi",
+            Style::Standard
+        )
+    );
+    assert_eq!(
+        Err(Error::UnknownByteword("aeadaolazmjendeot".to_string())),
+        decode("aeadaolazmjendeot", Style::Minimal)
+    );
+    assert_eq!(
+        Err(Error::InvalidBytewordsChecksum),
+        decode("ae", Style::Minimal)
+    );
+}
@@ -0,0 +1,297 @@
+use crate::encoding::{from_hex, to_hex};
+use crate::error::Error;
+use crate::search::Progress;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use std::fs;
+use std::time::{Duration, Instant};
+
+/**
+ * First line of checkpoint files
+ */
+const HEADER: &str = "# b39wc search checkpoint";
+
+/**
+ * Time between two saves of the progress of a search
+ */
+pub const SAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/**
+ * Progress of a search saved to be resumed later. The parameters of the
+ * search, which hold secrets such as known words or a passphrase, are
+ * only kept as a salted digest, unless their plaintext is asked for.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Command running the search
+    pub command: String,
+    salt: [u8; 16],
+    digest: [u8; 32],
+    /// Number of the next candidate, all the previous ones are checked
+    pub position: u128,
+    /// Number of candidates of the whole search
+    pub total: u128,
+    /// Names and values of the parameters, if they are kept as plaintext
+    pub parameters: Option<Vec<(String, String)>>,
+}
+
+fn digest(salt: &[u8; 16], command: &str, parameters: &[(&str, &str)], total: u128) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    for field in
+        std::iter::once(command).chain(parameters.iter().flat_map(|(name, value)| [*name, *value]))
+    {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.update(total.to_be_bytes());
+    hasher.finalize().into()
+}
+
+impl Checkpoint {
+    /**
+     * Starts the checkpoint of a new search
+     */
+    pub fn new<R: RngCore>(
+        command: &str,
+        parameters: &[(&str, &str)],
+        total: u128,
+        plaintext: bool,
+        rng: &mut R,
+    ) -> Checkpoint {
+        let mut salt = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        Checkpoint {
+            command: command.to_string(),
+            salt,
+            digest: digest(&salt, command, parameters, total),
+            position: 0,
+            total,
+            parameters: match plaintext {
+                true => Some(
+                    parameters
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect(),
+                ),
+                false => None,
+            },
+        }
+    }
+
+    /**
+     * Checks if the checkpoint was saved by the same search
+     */
+    pub fn belongs_to(&self, command: &str, parameters: &[(&str, &str)], total: u128) -> bool {
+        self.command == command
+            && self.total == total
+            && self.digest == digest(&self.salt, command, parameters, total)
+    }
+
+    /**
+     * Writes the checkpoint as lines of `name=value`
+     */
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            HEADER.to_string(),
+            format!("command={}", self.command),
+            format!("salt={}", to_hex(&self.salt)),
+            format!("digest={}", to_hex(&self.digest)),
+            format!("position={}", self.position),
+            format!("total={}", self.total),
+        ];
+        for (name, value) in self.parameters.iter().flatten() {
+            lines.push(format!("parameter.{}={}", name, value.escape_debug()));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /**
+     * Reads a checkpoint written by `to_text`
+     */
+    pub fn from_text(text: &str) -> Option<Checkpoint> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return None;
+        }
+        let fields: Vec<(&str, &str)> = lines.filter_map(|line| line.split_once('=')).collect();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| *value)
+        };
+        let parameters: Vec<(String, String)> = fields
+            .iter()
+            .filter_map(|(name, value)| {
+                let name = name.strip_prefix("parameter.")?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        Some(Checkpoint {
+            command: field("command")?.to_string(),
+            salt: from_hex(field("salt")?)?.try_into().ok()?,
+            digest: from_hex(field("digest")?)?.try_into().ok()?,
+            position: field("position")?.parse().ok()?,
+            total: field("total")?.parse().ok()?,
+            parameters: Some(parameters).filter(|parameters| !parameters.is_empty()),
+        })
+    }
+}
+
+/**
+ * A checkpoint saved in a file as a search goes on
+ */
+#[derive(Debug)]
+pub struct CheckpointFile {
+    path: String,
+    checkpoint: Checkpoint,
+    saved: Instant,
+}
+
+impl CheckpointFile {
+    /**
+     * Resumes the search saved in a file, or starts a new checkpoint
+     * if the file does not exist. Fails if the file was saved by
+     * another search.
+     */
+    pub fn open(
+        path: &str,
+        command: &str,
+        parameters: &[(&str, &str)],
+        total: u128,
+        plaintext: bool,
+    ) -> Result<CheckpointFile, Error> {
+        let checkpoint = match fs::read_to_string(path) {
+            Ok(text) => {
+                let checkpoint = Checkpoint::from_text(&text)
+                    .ok_or_else(|| Error::CheckpointLoad(path.to_string()))?;
+                if !checkpoint.belongs_to(command, parameters, total) {
+                    return Err(Error::CheckpointMismatch(path.to_string()));
+                }
+                checkpoint
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Checkpoint::new(
+                command,
+                parameters,
+                total,
+                plaintext,
+                &mut rand::thread_rng(),
+            ),
+            Err(_) => return Err(Error::CheckpointLoad(path.to_string())),
+        };
+        Ok(CheckpointFile {
+            path: path.to_string(),
+            checkpoint,
+            saved: Instant::now(),
+        })
+    }
+
+    /**
+     * Returns the number of the candidate the search resumes from
+     */
+    pub fn position(&self) -> u128 {
+        self.checkpoint.position
+    }
+
+    /**
+     * Records the progress of the search, saving it if it was not
+     * saved for a while or if the search is over
+     */
+    pub fn update(&mut self, progress: &Progress) -> Result<(), Error> {
+        self.checkpoint.position = progress.position;
+        if self.saved.elapsed() >= SAVE_INTERVAL || progress.position >= progress.total {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Saves the checkpoint, replacing the file at once so that an
+     * interruption never leaves it half written
+     */
+    pub fn save(&mut self) -> Result<(), Error> {
+        let temporary = format!("{}.tmp", self.path);
+        fs::write(&temporary, self.checkpoint.to_text())
+            .and_then(|_| fs::rename(&temporary, &self.path))
+            .map_err(|_| Error::CheckpointSave(self.path.clone()))?;
+        self.saved = Instant::now();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_checkpoint() {
+    use rand::rngs::mock::StepRng;
+
+    let parameters = [("phrase", "abandon ? about"), ("passphrase", "secret")];
+    let mut checkpoint =
+        Checkpoint::new("recover", &parameters, 2048, false, &mut StepRng::new(1, 1));
+    checkpoint.position = 1024;
+    let text = checkpoint.to_text();
+    assert!(text.starts_with(
+        "# b39wc search checkpoint\ncommand=recover\nsalt=01000000000000000200000000000000\n"
+    ));
+    assert!(text.ends_with("position=1024\ntotal=2048\n"));
+    assert!(!text.contains("abandon") && !text.contains("secret"));
+
+    let loaded = Checkpoint::from_text(&text).unwrap();
+    assert_eq!(checkpoint, loaded);
+    assert!(loaded.belongs_to("recover", &parameters, 2048));
+    assert!(!loaded.belongs_to("recover", &parameters, 4096));
+    assert!(!loaded.belongs_to("unscramble", &parameters, 2048));
+    assert!(!loaded.belongs_to("recover", &[("phrase", "abandon ? about")], 2048));
+    // Fields are delimited, so moving characters between them changes the digest
+    assert!(!loaded.belongs_to(
+        "recover",
+        &[("phrase", "abandon ? abou"), ("tpassphrase", "secret")],
+        2048
+    ));
+
+    let checkpoint = Checkpoint::new("recover", &parameters, 2048, true, &mut StepRng::new(1, 1));
+    let text = checkpoint.to_text();
+    assert!(text.ends_with("parameter.phrase=abandon ? about\nparameter.passphrase=secret\n"));
+    assert_eq!(Some(checkpoint), Checkpoint::from_text(&text));
+
+    assert_eq!(None, Checkpoint::from_text("command=recover\n"));
+    assert_eq!(
+        None,
+        Checkpoint::from_text(&text.replace("position=0", "position=x"))
+    );
+}
+
+#[test]
+fn test_checkpoint_file() {
+    let path = std::env::temp_dir().join(format!("b39wc-checkpoint-{}", std::process::id()));
+    let path = path.to_str().unwrap();
+    let parameters = [("phrase", "abandon ? about")];
+
+    let mut file = CheckpointFile::open(path, "recover", &parameters, 100, false).unwrap();
+    assert_eq!(0, file.position());
+    let progress = Progress {
+        position: 40,
+        total: 100,
+        checked: 40,
+        elapsed: Duration::from_secs(1),
+    };
+    // Saved only after a while
+    file.update(&progress).unwrap();
+    assert!(fs::metadata(path).is_err());
+    file.save().unwrap();
+
+    let resumed = CheckpointFile::open(path, "recover", &parameters, 100, false).unwrap();
+    assert_eq!(40, resumed.position());
+    assert_eq!(
+        Error::CheckpointMismatch(path.to_string()),
+        CheckpointFile::open(path, "recover", &[("phrase", "? ? about")], 100, false).unwrap_err()
+    );
+
+    fs::write(path, "not a checkpoint").unwrap();
+    assert_eq!(
+        Error::CheckpointLoad(path.to_string()),
+        CheckpointFile::open(path, "recover", &parameters, 100, false).unwrap_err()
+    );
+    fs::remove_file(path).unwrap();
+}
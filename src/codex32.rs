@@ -0,0 +1,402 @@
+use crate::error::Error;
+use crate::shamir::check_same;
+use std::fmt;
+use std::sync::OnceLock;
+
+/**
+ * Characters of the bech32 alphabet, in the order of their values
+ */
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/**
+ * Human readable part of codex32 strings holding master seeds
+ */
+const PREFIX: &str = "ms";
+
+/**
+ * Index of the share holding the secret
+ */
+pub const SECRET_INDEX: char = 's';
+
+/**
+ * Generators and targets of the checksums of codex32 strings :
+ * 13 characters for seeds up to 400 bits, 15 characters for longer ones
+ */
+const SHORT_GENERATOR: &str = "em3gqeeelmcss";
+const SHORT_TARGET: &str = "secretshare32";
+const LONG_GENERATOR: &str = "02e6fe4xh4x9kyh";
+const LONG_TARGET: &str = "secretshare32ex";
+
+/**
+ * Length of the threshold, identifier and share index
+ */
+const HEADER_LENGTH: usize = 6;
+
+/**
+ * Exponent and logarithm tables of GF(32) with the polynomial
+ * x^5 + x^3 + 1, built on first use
+ */
+fn tables() -> &'static ([u8; 31], [u8; 32]) {
+    static TABLES: OnceLock<([u8; 31], [u8; 32])> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 31];
+        let mut log = [0u8; 32];
+        let mut value: u8 = 1;
+        for (power, entry) in exp.iter_mut().enumerate() {
+            *entry = value;
+            log[value as usize] = power as u8;
+            // Multiplies by the generator x
+            value <<= 1;
+            if value & 0x20 != 0 {
+                value ^= 0x29;
+            }
+        }
+        (exp, log)
+    })
+}
+
+fn multiply(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = tables();
+    exp[(log[a as usize] as usize + log[b as usize] as usize) % 31]
+}
+
+fn divide(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+    let (exp, log) = tables();
+    exp[(log[a as usize] as usize + 31 - log[b as usize] as usize) % 31]
+}
+
+/**
+ * Returns the value of a lowercase bech32 character
+ */
+fn value(character: char) -> Option<u8> {
+    CHARSET.find(character).map(|index| index as u8)
+}
+
+fn character(value: u8) -> char {
+    CHARSET.as_bytes()[value as usize] as char
+}
+
+/**
+ * Computes the residue of the BCH code of a string : its values are
+ * fed to a polynomial reduced modulo the generator.
+ * The human readable part is expanded as bech32 does.
+ */
+fn residue(generator: &str, values: &[u8]) -> String {
+    let generator: Vec<u8> = generator.chars().filter_map(value).collect();
+    let mut residue = vec![0u8; generator.len()];
+    residue[generator.len() - 1] = 1;
+
+    let prefix = PREFIX.bytes();
+    let expanded = prefix
+        .clone()
+        .map(|byte| byte >> 5)
+        .chain(std::iter::once(0))
+        .chain(prefix.map(|byte| byte & 0x1F));
+    for input in expanded.chain(values.iter().copied()) {
+        let top = residue.remove(0);
+        residue.push(input);
+        for (coefficient, factor) in residue.iter_mut().zip(&generator) {
+            *coefficient ^= multiply(*factor, top);
+        }
+    }
+    residue.into_iter().map(character).collect()
+}
+
+/**
+ * A codex32 string (BIP-93) : either an unshared master seed,
+ * or one share of a seed split with Shamir's secret sharing.
+ * Threshold 0 means the seed is not shared.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub threshold: usize,
+    pub identifier: String,
+    pub index: char,
+    values: Vec<u8>,
+    checksum_length: usize,
+    uppercase: bool,
+}
+
+impl Share {
+    /**
+     * Reads a codex32 string and checks its case, length, checksum,
+     * threshold and padding
+     */
+    pub fn parse(string: &str) -> Result<Share, Error> {
+        let string = string.trim();
+        let uppercase = string.chars().any(|c| c.is_ascii_uppercase());
+        if uppercase && string.chars().any(|c| c.is_ascii_lowercase()) {
+            return Err(Error::Codex32Case);
+        }
+        let lowercase = string.to_ascii_lowercase();
+
+        let length = lowercase.chars().count();
+        let (generator, target) = match length {
+            48..=93 => (SHORT_GENERATOR, SHORT_TARGET),
+            125..=127 => (LONG_GENERATOR, LONG_TARGET),
+            _ => return Err(Error::Codex32Length(length)),
+        };
+        let data = lowercase
+            .strip_prefix(PREFIX)
+            .and_then(|data| data.strip_prefix('1'))
+            .ok_or(Error::Codex32Prefix)?;
+
+        let values: Vec<u8> = data
+            .chars()
+            .map(|c| value(c).ok_or(Error::Codex32Character(c)))
+            .collect::<Result<_, _>>()?;
+        if residue(generator, &values) != target {
+            return Err(Error::InvalidCodex32Checksum);
+        }
+
+        let mut header = data.chars();
+        let threshold = match header.next() {
+            Some('0') => 0,
+            Some(digit @ '2'..='9') => digit as usize - '0' as usize,
+            Some(other) => return Err(Error::Codex32Threshold(other)),
+            None => return Err(Error::Codex32Length(length)),
+        };
+        let identifier: String = header.by_ref().take(4).collect();
+        let index = header.next().unwrap_or(SECRET_INDEX);
+        if threshold == 0 && index != SECRET_INDEX {
+            return Err(Error::Codex32ShareIndex(index));
+        }
+
+        let share = Share {
+            threshold,
+            identifier,
+            index,
+            values,
+            checksum_length: target.len(),
+            uppercase,
+        };
+        if share.payload().len() * 5 % 8 > 4 {
+            return Err(Error::Codex32Padding);
+        }
+        Ok(share)
+    }
+
+    fn payload(&self) -> &[u8] {
+        &self.values[HEADER_LENGTH..self.values.len() - self.checksum_length]
+    }
+
+    /**
+     * Returns the bytes held by the share, without the padding bits
+     */
+    pub fn data(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+        for value in self.payload() {
+            buffer = (buffer << 5) | *value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+            }
+        }
+        bytes
+    }
+
+    /**
+     * Returns the codex32 string of the share, in its original case
+     */
+    pub fn encode(&self) -> String {
+        let string: String = self.values.iter().map(|value| character(*value)).collect();
+        let string = format!("{}1{}", PREFIX, string);
+        match self.uppercase {
+            true => string.to_ascii_uppercase(),
+            false => string,
+        }
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "threshold {}, identifier {}, share index {}",
+            self.threshold, self.identifier, self.index
+        )
+    }
+}
+
+/**
+ * Computes the share of some index from threshold shares, checksum
+ * included. Every character is interpolated in GF(32), the index of
+ * the shares being the value of their index character.
+ */
+pub fn interpolate(shares: &[Share], index: char) -> Result<Share, Error> {
+    check_same(shares, "identifiers", |share| share.identifier.clone())?;
+    check_same(shares, "thresholds", |share| share.threshold)?;
+    check_same(shares, "lengths", |share| share.values.len())?;
+    let target = value(index).ok_or(Error::Codex32Character(index))?;
+
+    let mut distinct: Vec<&Share> = Vec::new();
+    for share in shares {
+        match distinct.iter().find(|other| other.index == share.index) {
+            Some(other) if other.values != share.values => {
+                return Err(Error::Codex32DuplicateShare(share.index));
+            }
+            Some(_) => {}
+            None => distinct.push(share),
+        }
+    }
+
+    let threshold = shares.first().map(|share| share.threshold).unwrap_or(0);
+    if distinct.len() < threshold.max(1) {
+        return Err(Error::NotEnoughShares {
+            found: distinct.len(),
+            expected: threshold.max(1),
+        });
+    }
+    if let Some(share) = distinct.iter().find(|share| share.index == index) {
+        return Ok((*share).clone());
+    }
+    if threshold == 0 {
+        return Err(Error::Codex32ShareIndex(distinct[0].index));
+    }
+
+    let distinct = &distinct[..threshold];
+    let indexes: Vec<u8> = distinct
+        .iter()
+        .map(|share| value(share.index).unwrap_or(0))
+        .collect();
+    let mut values = vec![0u8; distinct[0].values.len()];
+    for (i, share) in distinct.iter().enumerate() {
+        // Lagrange basis polynomial of the share evaluated at the index
+        let basis = indexes
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1u8, |basis, (_, xj)| {
+                multiply(basis, divide(target ^ xj, indexes[i] ^ xj))
+            });
+        for (value, share_value) in values.iter_mut().zip(&share.values) {
+            *value ^= multiply(basis, *share_value);
+        }
+    }
+
+    Ok(Share {
+        index,
+        values,
+        ..distinct[0].clone()
+    })
+}
+
+/**
+ * Recovers the master seed from shares, or from the unshared seed.
+ * Shares must have the same identifier, threshold and length.
+ */
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, Error> {
+    Ok(interpolate(shares, SECRET_INDEX)?.data())
+}
+
+#[cfg(test)]
+fn parse_all(strings: &[&str]) -> Vec<Share> {
+    strings
+        .iter()
+        .map(|string| Share::parse(string).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_field() {
+    for a in 1..32u8 {
+        assert_eq!(a, divide(multiply(a, 7), 7));
+        assert_eq!(1, divide(a, a));
+    }
+    // x^5 = x^3 + 1
+    assert_eq!(0b01001, multiply(0b10000, 0b00010));
+}
+
+#[test]
+fn test_unshared_secret() {
+    use crate::encoding::to_hex;
+
+    let share = Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw").unwrap();
+    assert_eq!(0, share.threshold);
+    assert_eq!("test", share.identifier);
+    assert_eq!(SECRET_INDEX, share.index);
+    assert_eq!("318c6318c6318c6318c6318c6318c631", to_hex(&share.data()));
+
+    let long = Share::parse(
+        "MS100C8VSM32ZXFGUHPCHTLUPZRY9X8GF2TVDW0S3JN54KHCE6MUA7LQPZYGSFJD6AN074RXVCEMLH8WU3T\
+         K925ACDEFGHJKLMNPQRSTUVWXY06FHPV80UNDVARHRAK",
+    )
+    .unwrap();
+    assert_eq!(
+        "dc5423251cb87175ff8110c8531d0952d8d73e1194e95b5f19d6f9df7c01111104c9baecdfea8cccc\
+         677fb9ddc8aec5553b86e528bcadfdcc201c17c638c47e9",
+        to_hex(&combine(&[long]).unwrap())
+    );
+}
+
+#[test]
+fn test_invalid_strings() {
+    assert_eq!(
+        Err(Error::InvalidCodex32Checksum),
+        Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlx")
+    );
+    assert_eq!(
+        Err(Error::Codex32Case),
+        Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4NZVCA9CMCZLW")
+    );
+    assert_eq!(
+        Err(Error::Codex32Length(47)),
+        Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczl")
+    );
+    assert_eq!(
+        Err(Error::Codex32Character('b')),
+        Share::parse("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlb")
+    );
+}
+
+#[test]
+fn test_combine() {
+    use crate::encoding::to_hex;
+
+    let shares = parse_all(&[
+        "MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM",
+        "MS12NAMECACDEFGHJKLMNPQRSTUVWXYZ023FTR2GDZMPY6PN",
+    ]);
+    assert_eq!(
+        "MS12NAMES6XQGUZTTXKEQNJSJZV4JV3NZ5K3KWGSPHUH6EVW",
+        interpolate(&shares, SECRET_INDEX).unwrap().encode()
+    );
+    assert_eq!(
+        "d1808e096b35b209ca12132b264662a5",
+        to_hex(&combine(&shares).unwrap())
+    );
+    assert_eq!(
+        Err(Error::NotEnoughShares {
+            found: 1,
+            expected: 2
+        }),
+        combine(&shares[..1])
+    );
+
+    let shares = parse_all(&[
+        "ms13casha320zyxwvutsrqpnmlkjhgfedca2a8d0zehn8a0t",
+        "ms13cashcacdefghjklmnpqrstuvwxyz023949xq35my48dr",
+        "ms13cashd0wsedstcdcts64cd7wvy4m90lm28w4ffupqs7rm",
+    ]);
+    assert_eq!(
+        "ms13cashsllhdmn9m42vcsamx24zrxgs3qqjzqud4m0d6nln",
+        interpolate(&shares, SECRET_INDEX).unwrap().encode()
+    );
+    assert_eq!(
+        "ms13casheekgpemxzshcrmqhaydlp6yhms3ws7320xyxsar9",
+        interpolate(&shares, 'e').unwrap().encode()
+    );
+    assert_eq!(
+        "ffeeddccbbaa99887766554433221100",
+        to_hex(&combine(&shares).unwrap())
+    );
+}
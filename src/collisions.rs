@@ -0,0 +1,154 @@
+use crate::language::Language;
+use crate::wordlist::Wordlist;
+use std::collections::HashMap;
+
+/**
+ * Words shared by two built-in dictionaries
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    pub first: Language,
+    pub second: Language,
+    pub words: Vec<&'static str>,
+}
+
+/**
+ * Returns the words of a dictionary also found in another one,
+ * in the order of the first dictionary
+ */
+pub fn shared_words<'a>(first: &'a dyn Wordlist, second: &dyn Wordlist) -> Vec<&'a str> {
+    (0..first.len())
+        .filter_map(|index| first.word(index))
+        .filter(|word| second.contains(word))
+        .collect()
+}
+
+/**
+ * Computes the words shared between every pair of built-in dictionaries.
+ * Pairs without any shared word are left out.
+ */
+pub fn collisions() -> Vec<Collision> {
+    let mut collisions = Vec::new();
+    for (i, &first) in Language::ALL.iter().enumerate() {
+        for &second in Language::ALL.iter().skip(i + 1) {
+            let words: Vec<&'static str> = first
+                .words()
+                .iter()
+                .copied()
+                .filter(|word| Wordlist::contains(&second, word))
+                .collect();
+            if !words.is_empty() {
+                collisions.push(Collision {
+                    first,
+                    second,
+                    words,
+                });
+            }
+        }
+    }
+    collisions
+}
+
+/**
+ * Computes the probability that a phrase of random words from a dictionary
+ * is made only of words of at least one of the other dictionaries,
+ * so the dictionary can't be told from the words alone.
+ * The checksum is not taken into account.
+ */
+pub fn ambiguity_probability(
+    wordlist: &dyn Wordlist,
+    others: &[&dyn Wordlist],
+    length: usize,
+) -> f64 {
+    if wordlist.is_empty() || others.is_empty() {
+        return 0.0;
+    }
+
+    // Number of words for each set of other dictionaries holding them
+    let mut masks: HashMap<u64, usize> = HashMap::new();
+    for word in (0..wordlist.len()).filter_map(|index| wordlist.word(index)) {
+        let mask = others
+            .iter()
+            .take(64)
+            .enumerate()
+            .filter(|(_, other)| other.contains(word))
+            .fold(0u64, |mask, (i, _)| mask | 1 << i);
+        if mask != 0 {
+            *masks.entry(mask).or_insert(0) += 1;
+        }
+    }
+
+    // Inclusion-exclusion over the sets of other dictionaries
+    // that could hold all the words of the phrase
+    let size = wordlist.len() as f64;
+    let union = masks.keys().fold(0u64, |union, mask| union | mask);
+    let mut probability = 0.0;
+    let mut subset = union;
+    while subset != 0 {
+        let count: usize = masks
+            .iter()
+            .filter(|(&mask, _)| mask & subset == subset)
+            .map(|(_, &count)| count)
+            .sum();
+        let term = (count as f64 / size).powi(length as i32);
+        if subset.count_ones() % 2 == 1 {
+            probability += term;
+        } else {
+            probability -= term;
+        }
+        subset = (subset - 1) & union;
+    }
+
+    probability.clamp(0.0, 1.0)
+}
+
+/**
+ * Computes the ambiguity probability of a built-in dictionary
+ * against all the other built-in dictionaries
+ */
+pub fn builtin_ambiguity_probability(language: Language, length: usize) -> f64 {
+    let others: Vec<&dyn Wordlist> = Language::ALL
+        .iter()
+        .filter(|&&other| other != language)
+        .map(|other| other as &dyn Wordlist)
+        .collect();
+    ambiguity_probability(&language, &others, length)
+}
+
+#[test]
+fn test_collisions() {
+    let collisions = collisions();
+    let english_french = collisions
+        .iter()
+        .find(|c| c.first == Language::English && c.second == Language::French)
+        .unwrap();
+    assert_eq!(100, english_french.words.len());
+    assert!(english_french.words.contains(&"abandon"));
+    assert!(english_french.words.contains(&"animal"));
+    assert!(collisions
+        .iter()
+        .all(|c| c.first != Language::English || c.second != Language::Italian));
+}
+
+#[test]
+fn test_shared_words() {
+    let words = shared_words(&Language::English, &Language::French);
+    assert_eq!(100, words.len());
+    assert_eq!("abandon", words[0]);
+    assert!(shared_words(&Language::English, &Language::Korean).is_empty());
+}
+
+#[test]
+fn test_ambiguity_probability() {
+    let english = builtin_ambiguity_probability(Language::English, 12);
+    assert!((english - (100.0f64 / 2048.0).powi(12)).abs() < 1e-20);
+    assert_eq!(0.0, builtin_ambiguity_probability(Language::Italian, 12));
+    assert!(
+        builtin_ambiguity_probability(Language::ChineseSimplified, 12)
+            > builtin_ambiguity_probability(Language::ChineseSimplified, 24)
+    );
+
+    // A phrase from a dictionary entirely shared is always ambiguous
+    let others: Vec<&dyn Wordlist> = vec![&Language::English];
+    assert_eq!(1.0, ambiguity_probability(&Language::English, &others, 24));
+}
@@ -0,0 +1,141 @@
+use super::read_phrase;
+use b39wc::bip32::ExtendedKey;
+use b39wc::bip85;
+use b39wc::encoding::to_hex;
+use b39wc::{Error, Language};
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::str::FromStr;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("bip85")
+        .about("Derives BIP-85 child phrases, keys and passwords from a phrase")
+        .arg(
+            Arg::with_name("phrase")
+                .required(true)
+                .help("The master phrase"),
+        )
+        .arg(
+            Arg::with_name("application")
+                .short("a")
+                .long("application")
+                .value_name("application")
+                .help("What to derive")
+                .possible_values(&["bip39", "wif", "hex", "base64", "base85"])
+                .default_value("bip39")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("index")
+                .short("i")
+                .long("index")
+                .value_name("index")
+                .help("Index of the derived secret")
+                .default_value("0")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("words")
+                .short("w")
+                .long("words")
+                .value_name("words")
+                .help("Number of words of a child phrase")
+                .possible_values(&["12", "15", "18", "21", "24"])
+                .default_value("24")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("language")
+                .short("l")
+                .long("language")
+                .value_name("language")
+                .help("Language of a child phrase")
+                .default_value("english")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("length")
+                .long("length")
+                .value_name("length")
+                .help("Number of hex bytes or password characters")
+                .required_ifs(&[
+                    ("application", "hex"),
+                    ("application", "base64"),
+                    ("application", "base85"),
+                ])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("passphrase")
+                .short("p")
+                .long("passphrase")
+                .value_name("passphrase")
+                .help("Passphrase of the master phrase")
+                .takes_value(true),
+        )
+}
+
+/**
+ * Numbers given as options
+ */
+struct Numbers {
+    index: u32,
+    words: usize,
+    length: usize,
+}
+
+fn numbers(matches: &ArgMatches) -> Result<Numbers, clap::Error> {
+    Ok(Numbers {
+        index: value_t!(matches, "index", u32)?,
+        words: value_t!(matches, "words", usize)?,
+        length: match matches.is_present("length") {
+            true => value_t!(matches, "length", usize)?,
+            false => 0,
+        },
+    })
+}
+
+fn derive(matches: &ArgMatches, numbers: &Numbers) -> Result<String, Error> {
+    let (mnemonic, language) = read_phrase(matches.value_of("phrase").unwrap_or(""))?;
+    let seed = mnemonic.to_seed(&language, matches.value_of("passphrase").unwrap_or(""))?;
+    let master = ExtendedKey::from_seed(&seed)?;
+    let index = numbers.index;
+
+    match matches.value_of("application").unwrap_or("bip39") {
+        "wif" => bip85::wif(&master, index),
+        "hex" => bip85::hex(&master, numbers.length, index).map(|bytes| to_hex(&bytes)),
+        "base64" => bip85::base64_password(&master, numbers.length, index),
+        "base85" => bip85::base85_password(&master, numbers.length, index),
+        _ => {
+            let child_language =
+                Language::from_str(matches.value_of("language").unwrap_or("english"))?;
+            let child = bip85::bip39(&master, child_language, numbers.words, index)?;
+            Ok(child.to_phrase(&child_language))
+        }
+    }
+}
+
+/**
+ * Runs the bip85 command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let numbers = match numbers(matches) {
+        Ok(numbers) => numbers,
+        Err(error) => {
+            red_ln!("{}", error.message);
+            return 1;
+        }
+    };
+
+    match derive(matches, &numbers) {
+        Ok(derived) => {
+            green_ln!("{}", derived);
+            0
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use b39wc::builder::{build_wordlist, to_rust, to_text, BuildRules};
+use b39wc::Error;
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::fs;
+use std::path::Path;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("build-wordlist")
+        .about("Selects the words of a new BIP-39 dictionary from a corpus")
+        .arg(
+            Arg::with_name("corpus")
+                .required(true)
+                .help("Text file holding candidate words, most common first"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .value_name("path")
+                .required(true)
+                .help("Plain text file to write, one word per line"),
+        )
+        .arg(
+            Arg::with_name("rust")
+                .long("rust")
+                .value_name("path")
+                .help("Rust source file to write (defaults to output with .rs extension)"),
+        )
+        .arg(
+            Arg::with_name("name")
+                .long("name")
+                .value_name("name")
+                .default_value("custom")
+                .help("Name of the dictionary in the Rust source file"),
+        )
+        .arg(number_arg("size", "Number of words to select", "2048"))
+        .arg(number_arg(
+            "min-length",
+            "Minimum number of characters of a word",
+            "3",
+        ))
+        .arg(number_arg(
+            "max-length",
+            "Maximum number of characters of a word",
+            "8",
+        ))
+        .arg(number_arg(
+            "prefix-length",
+            "Number of leading characters unique to each word",
+            "4",
+        ))
+        .arg(number_arg(
+            "min-distance",
+            "Minimum edit distance between two words",
+            "2",
+        ))
+        .arg(
+            Arg::with_name("allow-builtin")
+                .long("allow-builtin")
+                .help("Allows words found in the built-in dictionaries"),
+        )
+}
+
+fn number_arg<'a, 'b>(name: &'a str, help: &'a str, default: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name("n")
+        .default_value(default)
+        .help(help)
+}
+
+fn rules(matches: &ArgMatches) -> Result<BuildRules, clap::Error> {
+    Ok(BuildRules {
+        size: value_t!(matches, "size", usize)?,
+        min_length: value_t!(matches, "min-length", usize)?,
+        max_length: value_t!(matches, "max-length", usize)?,
+        prefix_length: value_t!(matches, "prefix-length", usize)?,
+        min_distance: value_t!(matches, "min-distance", usize)?,
+        exclude_builtin: !matches.is_present("allow-builtin"),
+    })
+}
+
+fn write(path: &str, content: &str) -> Result<(), Error> {
+    fs::write(path, content).map_err(|_| Error::FileWrite(path.to_string()))
+}
+
+/**
+ * Runs the build-wordlist command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let rules = match rules(matches) {
+        Ok(rules) => rules,
+        Err(error) => {
+            red_ln!("{}", error.message);
+            return 1;
+        }
+    };
+
+    let corpus_path = matches.value_of("corpus").unwrap_or_default();
+    let corpus = match fs::read_to_string(corpus_path) {
+        Ok(corpus) => corpus,
+        Err(_) => {
+            red_ln!("Error loading corpus with path : {}", corpus_path);
+            return 1;
+        }
+    };
+
+    let output = matches.value_of("output").unwrap_or_default();
+    let rust_output = match matches.value_of("rust") {
+        Some(path) => path.to_string(),
+        None => Path::new(output)
+            .with_extension("rs")
+            .to_string_lossy()
+            .to_string(),
+    };
+    let name = matches.value_of("name").unwrap_or("custom");
+
+    let result = build_wordlist(&corpus, &rules).and_then(|words| {
+        write(output, &to_text(&words))?;
+        write(&rust_output, &to_rust(name, &words))?;
+        Ok(words)
+    });
+
+    match result {
+        Ok(words) => {
+            green_ln!(
+                "{} words written to {} and {}",
+                words.len(),
+                output,
+                rust_output
+            );
+            0
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use b39wc::codex32::{combine, Share};
+use b39wc::encoding::to_hex;
+use b39wc::{Language, Mnemonic};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use colour::yellow_ln;
+use std::str::FromStr;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("codex32")
+        .about("Checks codex32 shares and recovers their master seed")
+        .arg(
+            Arg::with_name("shares")
+                .required(true)
+                .multiple(true)
+                .help("The codex32 strings to check, one argument per share"),
+        )
+        .arg(
+            Arg::with_name("language")
+                .short("l")
+                .long("language")
+                .value_name("language")
+                .help("Language of the BIP-39 phrase of the master seed")
+                .default_value("english")
+                .takes_value(true),
+        )
+}
+
+/**
+ * Runs the codex32 command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let language = match Language::from_str(matches.value_of("language").unwrap_or("english")) {
+        Ok(language) => language,
+        Err(error) => {
+            red_ln!("{}", error);
+            return 1;
+        }
+    };
+
+    let mut shares: Vec<Share> = Vec::new();
+    let mut valid = true;
+    for (index, string) in matches.values_of("shares").unwrap().enumerate() {
+        match Share::parse(string) {
+            Ok(share) => {
+                green_ln!("Share {} is valid : {}", index + 1, share);
+                shares.push(share);
+            }
+            Err(error) => {
+                red_ln!("Share {} is invalid : {}", index + 1, error);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        return 1;
+    }
+
+    let seed = match combine(&shares) {
+        Ok(seed) => seed,
+        Err(error) => {
+            red_ln!("{}", error);
+            return 1;
+        }
+    };
+    green_ln!("Master seed : {}", to_hex(&seed));
+    match Mnemonic::from_entropy(&seed, &language) {
+        Ok(mnemonic) => {
+            green_ln!("BIP-39 phrase ({}) : {}", language.name(), mnemonic);
+        }
+        Err(error) => {
+            yellow_ln!("Master seed has no BIP-39 phrase : {}", error);
+        }
+    }
+    0
+}
@@ -0,0 +1,51 @@
+use b39wc::collisions::{builtin_ambiguity_probability, collisions};
+use b39wc::{Language, Mnemonic};
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("collisions")
+        .about("Reports the words shared between built-in dictionaries")
+        .arg(
+            Arg::with_name("words")
+                .short("w")
+                .long("words")
+                .help("Lists the shared words"),
+        )
+}
+
+/**
+ * Runs the collisions command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    for collision in collisions() {
+        println!(
+            "{} / {} : {} shared words",
+            collision.first,
+            collision.second,
+            collision.words.len()
+        );
+        if matches.is_present("words") {
+            println!("    {}", collision.words.join(", "));
+        }
+    }
+
+    println!();
+    println!("Probability that a random phrase also belongs to another dictionary :");
+    print!("{:<20}", "");
+    for length in Mnemonic::VALID_LENGTHS.iter() {
+        print!("{:>12}", format!("{} words", length));
+    }
+    println!();
+    for &language in Language::ALL.iter() {
+        print!("{:<20}", language.name());
+        for &length in Mnemonic::VALID_LENGTHS.iter() {
+            print!(
+                "{:>12}",
+                format!("{:.2e}", builtin_ambiguity_probability(language, length))
+            );
+        }
+        println!();
+    }
+
+    0
+}
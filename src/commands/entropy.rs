@@ -0,0 +1,82 @@
+use b39wc::entropy::{Rolls, Source};
+use b39wc::{Error, Language, Mnemonic};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::str::FromStr;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("entropy")
+        .about("Builds a phrase from dice rolls, coin flips or a shuffled deck of cards")
+        .arg(
+            Arg::with_name("input")
+                .required(true)
+                .help("The dice rolls (1 to 6), coin flips (h or t) or cards (as, 10h, kd...)"),
+        )
+        .arg(
+            Arg::with_name("source")
+                .long("source")
+                .value_name("source")
+                .help("Source of the entropy")
+                .possible_values(&["dice", "coins", "cards"])
+                .default_value("dice")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("words")
+                .short("w")
+                .long("words")
+                .value_name("words")
+                .help("Number of words of the phrase")
+                .possible_values(&["12", "15", "18", "21", "24"])
+                .default_value("24")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("language")
+                .short("l")
+                .long("language")
+                .value_name("language")
+                .help("Language of the phrase")
+                .default_value("english")
+                .takes_value(true),
+        )
+}
+
+fn build(matches: &ArgMatches) -> Result<(), Error> {
+    let language = Language::from_str(matches.value_of("language").unwrap_or("english"))?;
+    let source = Source::from_str(matches.value_of("source").unwrap_or("dice"))?;
+    let words: usize = matches
+        .value_of("words")
+        .and_then(|words| words.parse().ok())
+        .unwrap_or(24);
+
+    let rolls = Rolls::parse(source, matches.value_of("input").unwrap_or(""))?;
+    println!(
+        "{} {}s read : {:.1} bits of entropy",
+        rolls.len(),
+        source.symbol_name(),
+        rolls.bits()
+    );
+    let entropy = rolls.entropy(words)?;
+    let mnemonic = Mnemonic::from_entropy(&entropy, &language)?;
+    green_ln!(
+        "Phrase ({}) : {}",
+        language.name(),
+        mnemonic.to_phrase(&language)
+    );
+    Ok(())
+}
+
+/**
+ * Runs the entropy command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    match build(matches) {
+        Ok(()) => 0,
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use b39wc::lint::{lint_file, LintIssue, Severity};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use colour::yellow_ln;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("lint-dictionary")
+        .about("Checks that dictionary files can be used for BIP-39")
+        .arg(
+            Arg::with_name("paths")
+                .multiple(true)
+                .required(true)
+                .help("Dictionary files to check"),
+        )
+}
+
+/**
+ * Prints the issues found in a dictionary.
+ * Returns false if one of them prevents the dictionary from being used.
+ */
+fn report(path: &str, issues: &[LintIssue]) -> bool {
+    let mut valid = true;
+    for issue in issues {
+        match issue.severity() {
+            Severity::Error => {
+                valid = false;
+                red_ln!("{} : {}", path, issue);
+            }
+            Severity::Warning => {
+                yellow_ln!("{} : {}", path, issue);
+            }
+        }
+    }
+    valid
+}
+
+/**
+ * Checks the dictionaries provided with the seed.
+ * Returns false if one of them can't be used.
+ */
+pub fn check_dictionaries(paths: &[&str]) -> bool {
+    let mut valid = true;
+    for path in paths {
+        match lint_file(path) {
+            Ok(issues) => valid &= report(path, &issues),
+            Err(error) => {
+                red_ln!("{}", error);
+                valid = false;
+            }
+        }
+    }
+    valid
+}
+
+/**
+ * Runs the lint-dictionary command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let paths: Vec<&str> = matches.values_of("paths").unwrap_or_default().collect();
+    let mut valid = true;
+
+    for path in paths {
+        match lint_file(path) {
+            Ok(issues) if issues.is_empty() => {
+                green_ln!("{} : dictionary is valid", path);
+            }
+            Ok(issues) => valid &= report(path, &issues),
+            Err(error) => {
+                red_ln!("{}", error);
+                valid = false;
+            }
+        }
+    }
+
+    if valid {
+        0
+    } else {
+        1
+    }
+}
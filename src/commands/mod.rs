@@ -0,0 +1,48 @@
+pub mod bip85;
+pub mod build;
+pub mod codex32;
+pub mod collisions;
+pub mod entropy;
+pub mod lint;
+pub mod passphrase;
+pub mod recover;
+pub mod seedqr;
+pub mod seedxor;
+pub mod slip39;
+pub mod sskr;
+pub mod unscramble;
+pub mod ur;
+
+use b39wc::lenient::normalize_words;
+use b39wc::tokenizer::tokenize;
+use b39wc::{builtin_wordlists, Error, Language, Mnemonic};
+use colour::red_ln;
+
+/**
+ * Reads a phrase of the built-in dictionaries and returns it along
+ * with the language its checksum is valid in
+ */
+pub fn read_phrase(phrase: &str) -> Result<(Mnemonic, Language), Error> {
+    let wordlists = builtin_wordlists();
+    let (words, _) = normalize_words(&tokenize(phrase).words, &wordlists);
+    let mnemonic = Mnemonic::from_words(words);
+    mnemonic.check(&wordlists)?;
+    let language = Language::ALL
+        .iter()
+        .find(|language| mnemonic.verify_checksum(*language).is_ok())
+        .ok_or(Error::InvalidChecksum)?;
+    Ok((mnemonic, *language))
+}
+
+/**
+ * Prints the error of a command, if any, and returns its exit code
+ */
+pub fn report(result: Result<(), Error>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::read_phrase;
+use super::recover::{
+    checkpoint_args, close_checkpoint, numbers, open_checkpoint, option_values, print_progress,
+    read_oracle, save_progress, Numbers,
+};
+use b39wc::checkpoint::CheckpointFile;
+use b39wc::encoding::to_hex;
+use b39wc::recovery::recover_passphrase;
+use b39wc::typos::Typos;
+use b39wc::Error;
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+/**
+ * Options of a passphrase search saved in its checkpoint,
+ * along with the digest of its candidates
+ */
+const SEARCH_OPTIONS: [&str; 4] = ["phrase", "target", "path", "gap"];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    let app = SubCommand::with_name("passphrase")
+        .about("Recovers the passphrase of a phrase from a list of candidates and their typos")
+        .arg(
+            Arg::with_name("phrase")
+                .required(true)
+                .help("The phrase protected by the passphrase"),
+        )
+        .arg(
+            Arg::with_name("candidates")
+                .short("c")
+                .long("candidates")
+                .value_name("file")
+                .help("File of candidate passphrases, one per line")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("target")
+                .short("t")
+                .long("target")
+                .value_name("target")
+                .help("Master fingerprint, xpub, ypub, zpub or address of the phrase")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .value_name("path")
+                .help("Derivation path of the xpub, or of the parent of the addresses")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gap")
+                .long("gap")
+                .value_name("gap")
+                .help("Number of addresses checked under the path")
+                .default_value("20")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("no-typos")
+                .long("no-typos")
+                .help("Only tries the candidates as they are written"),
+        )
+        .arg(
+            Arg::with_name("digits")
+                .long("digits")
+                .value_name("digits")
+                .help("Maximum number of digits appended to the candidates")
+                .possible_values(&["0", "1", "2", "3", "4"])
+                .default_value("1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("threads")
+                .short("j")
+                .long("threads")
+                .value_name("threads")
+                .help("Number of threads, all the cores by default")
+                .takes_value(true),
+        );
+    checkpoint_args(app)
+}
+
+fn search(matches: &ArgMatches, numbers: &Numbers, typos: &Typos) -> Result<bool, Error> {
+    let (mnemonic, language) = read_phrase(matches.value_of("phrase").unwrap_or(""))?;
+    let path = matches.value_of("candidates").unwrap_or("");
+    let list = fs::read_to_string(path).map_err(|_| Error::PassphrasesLoad(path.to_string()))?;
+    let passphrases = typos.candidates(&list);
+    let oracle = match read_oracle(matches, numbers)? {
+        Some(oracle) => oracle,
+        None => return Ok(false),
+    };
+
+    println!(
+        "Trying {} passphrases against the {}",
+        passphrases.len(),
+        oracle
+    );
+    let mut parameters = option_values(matches, &SEARCH_OPTIONS);
+    parameters.push((
+        "passphrases",
+        to_hex(&Sha256::digest(passphrases.join("\n").as_bytes())),
+    ));
+    let mut checkpoint = open_checkpoint(
+        matches,
+        "passphrase",
+        &parameters,
+        passphrases.len() as u128,
+    )?;
+    let found = recover_passphrase(
+        &mnemonic,
+        &language,
+        &passphrases,
+        &oracle,
+        checkpoint.as_ref().map_or(0, CheckpointFile::position),
+        numbers.threads,
+        &mut |progress| {
+            print_progress(progress);
+            save_progress(&mut checkpoint, progress)
+        },
+    );
+    eprintln!();
+    close_checkpoint(checkpoint)?;
+
+    match found {
+        Some(passphrase) => {
+            green_ln!("Passphrase : {:?}", passphrase);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/**
+ * Runs the passphrase command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let options = numbers(matches).and_then(|numbers| {
+        let typos = match matches.is_present("no-typos") {
+            true => Typos::none(),
+            false => Typos {
+                digits: value_t!(matches, "digits", u32)?,
+                ..Typos::default()
+            },
+        };
+        Ok((numbers, typos))
+    });
+    let (numbers, typos) = match options {
+        Ok(options) => options,
+        Err(error) => {
+            red_ln!("{}", error.message);
+            return 1;
+        }
+    };
+
+    match search(matches, &numbers, &typos) {
+        Ok(true) => 0,
+        Ok(false) => {
+            red_ln!("No passphrase matches the target");
+            1
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
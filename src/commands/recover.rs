@@ -0,0 +1,308 @@
+use b39wc::bip32::parse_path;
+use b39wc::checkpoint::CheckpointFile;
+use b39wc::oracle::{Oracle, Target};
+use b39wc::recovery::{recover_first, Candidates, WordPattern};
+use b39wc::search::{format_duration, Progress};
+use b39wc::{Error, Language};
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::io::Write;
+use std::str::FromStr;
+use std::thread;
+
+/**
+ * Options telling a search apart, kept as a digest in its checkpoint.
+ * Each recovery command has its own list.
+ */
+const SEARCH_OPTIONS: [&str; 6] = ["phrase", "target", "path", "gap", "passphrase", "language"];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    let app =
+        SubCommand::with_name("recover")
+            .about("Recovers the missing words of a phrase with an address, xpub or fingerprint")
+            .arg(Arg::with_name("phrase").required(true).help(
+                "The phrase, with ? for a missing word, ab* for a prefix, a|b for candidates",
+            ))
+            .arg(
+                Arg::with_name("target")
+                    .short("t")
+                    .long("target")
+                    .value_name("target")
+                    .help("Master fingerprint, xpub, ypub, zpub or address of the phrase")
+                    .required(true)
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("path")
+                    .long("path")
+                    .value_name("path")
+                    .help("Derivation path of the xpub, or of the parent of the addresses")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("gap")
+                    .long("gap")
+                    .value_name("gap")
+                    .help("Number of addresses checked under the path")
+                    .default_value("20")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("passphrase")
+                    .short("p")
+                    .long("passphrase")
+                    .value_name("passphrase")
+                    .help("Passphrase of the phrase")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("language")
+                    .short("l")
+                    .long("language")
+                    .value_name("language")
+                    .help("Language of the phrase")
+                    .default_value("english")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("force")
+                    .long("force")
+                    .help("Searches even if the search would take weeks or more"),
+            )
+            .arg(
+                Arg::with_name("threads")
+                    .short("j")
+                    .long("threads")
+                    .value_name("threads")
+                    .help("Number of threads, all the cores by default")
+                    .takes_value(true),
+            );
+    checkpoint_args(app)
+}
+
+/**
+ * Adds the options saving the progress of a search to a recovery command
+ */
+pub fn checkpoint_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
+    app.arg(
+        Arg::with_name("checkpoint")
+            .long("checkpoint")
+            .value_name("file")
+            .help("File saving the progress of the search, resumed if it exists")
+            .takes_value(true),
+    )
+    .arg(
+        Arg::with_name("checkpoint-plaintext")
+            .long("checkpoint-plaintext")
+            .help("Writes the phrase, passphrase and target in the checkpoint as plaintext")
+            .requires("checkpoint"),
+    )
+}
+
+/**
+ * Numbers given as options
+ */
+pub struct Numbers {
+    pub gap: u32,
+    pub threads: usize,
+}
+
+/**
+ * Reads the numbers given as options of the recovery commands
+ */
+pub fn numbers(matches: &ArgMatches) -> Result<Numbers, clap::Error> {
+    Ok(Numbers {
+        gap: value_t!(matches, "gap", u32)?,
+        threads: match matches.is_present("threads") {
+            true => value_t!(matches, "threads", usize)?,
+            false => thread::available_parallelism().map_or(1, |threads| threads.get()),
+        },
+    })
+}
+
+/**
+ * Builds the oracle of the target given as option, if any
+ */
+pub fn read_oracle(matches: &ArgMatches, numbers: &Numbers) -> Result<Option<Oracle>, Error> {
+    let target = match matches.value_of("target") {
+        Some(target) => Target::parse(target)?,
+        None => return Ok(None),
+    };
+    let path = matches.value_of("path").map(parse_path).transpose()?;
+    Oracle::new(target, path, numbers.gap).map(Some)
+}
+
+/**
+ * Returns the values of options, joined by commas when repeated
+ */
+pub fn option_values<'a>(matches: &ArgMatches, options: &[&'a str]) -> Vec<(&'a str, String)> {
+    options
+        .iter()
+        .map(|option| {
+            let values = matches
+                .values_of(option)
+                .map(|values| values.collect::<Vec<&str>>().join(","))
+                .unwrap_or_default();
+            (*option, values)
+        })
+        .collect()
+}
+
+/**
+ * Opens the checkpoint file given as option, if any. The search is
+ * told apart by its parameters, such as the values of its options.
+ */
+pub fn open_checkpoint(
+    matches: &ArgMatches,
+    command: &str,
+    parameters: &[(&str, String)],
+    total: u128,
+) -> Result<Option<CheckpointFile>, Error> {
+    let path = match matches.value_of("checkpoint") {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let parameters: Vec<(&str, &str)> = parameters
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+
+    let checkpoint = CheckpointFile::open(
+        path,
+        command,
+        &parameters,
+        total,
+        matches.is_present("checkpoint-plaintext"),
+    )?;
+    if checkpoint.position() > 0 {
+        println!(
+            "Resuming from candidate {} of {}",
+            checkpoint.position(),
+            total
+        );
+    }
+    Ok(Some(checkpoint))
+}
+
+/**
+ * Records the progress of a search in its checkpoint, if any,
+ * and returns whether the search goes on
+ */
+pub fn save_progress(checkpoint: &mut Option<CheckpointFile>, progress: &Progress) -> bool {
+    match checkpoint
+        .as_mut()
+        .map(|checkpoint| checkpoint.update(progress))
+    {
+        Some(Err(error)) => {
+            eprintln!();
+            red_ln!("{}", error);
+            false
+        }
+        _ => true,
+    }
+}
+
+/**
+ * Saves the checkpoint of a search at its end
+ */
+pub fn close_checkpoint(checkpoint: Option<CheckpointFile>) -> Result<(), Error> {
+    match checkpoint {
+        Some(mut checkpoint) => checkpoint.save(),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Writes the progress of a search on the same line of the error output
+ */
+pub fn print_progress(progress: &Progress) {
+    let eta = progress
+        .eta()
+        .map_or("unknown".to_string(), format_duration);
+    eprint!(
+        "\r{:.1}% checked, {:.0} candidates/s, {} left      ",
+        progress.percentage(),
+        progress.rate(),
+        eta
+    );
+    std::io::stderr().flush().ok();
+}
+
+fn search(matches: &ArgMatches, numbers: &Numbers) -> Result<bool, Error> {
+    let language = Language::from_str(matches.value_of("language").unwrap_or("english"))?;
+    let pattern = WordPattern::parse(matches.value_of("phrase").unwrap_or(""), &language)?;
+    let oracle = match read_oracle(matches, numbers)? {
+        Some(oracle) => oracle,
+        None => return Ok(false),
+    };
+
+    println!(
+        "Searching {} phrases for words {:?}, about {} with a valid checksum",
+        pattern.count(),
+        pattern.unknown_positions(),
+        pattern.seeds()
+    );
+    if !pattern.is_feasible(true) {
+        if !matches.is_present("force") {
+            red_ln!("This search would take weeks or more, give more words or use --force");
+            return Ok(false);
+        }
+        red_ln!("This search would take weeks or more");
+    }
+    println!("Checking them against the {}", oracle);
+    let mut checkpoint = open_checkpoint(
+        matches,
+        "recover",
+        &option_values(matches, &SEARCH_OPTIONS),
+        pattern.count(),
+    )?;
+    let start = checkpoint.as_ref().map_or(0, CheckpointFile::position);
+    let found = recover_first(
+        &pattern,
+        &language,
+        matches.value_of("passphrase").unwrap_or(""),
+        &oracle,
+        start,
+        numbers.threads,
+        &mut |progress| {
+            print_progress(progress);
+            save_progress(&mut checkpoint, progress)
+        },
+    );
+    eprintln!();
+    close_checkpoint(checkpoint)?;
+
+    match found {
+        Some(mnemonic) => {
+            green_ln!("Match : {}", mnemonic.to_phrase(&language));
+            Ok(true)
+        }
+        None => {
+            red_ln!("No phrase matches the target");
+            Ok(false)
+        }
+    }
+}
+
+/**
+ * Runs the recover command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let numbers = match numbers(matches) {
+        Ok(numbers) => numbers,
+        Err(error) => {
+            red_ln!("{}", error.message);
+            return 1;
+        }
+    };
+
+    match search(matches, &numbers) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
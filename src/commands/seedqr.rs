@@ -0,0 +1,145 @@
+use b39wc::encoding::{from_hex, to_hex};
+use b39wc::lenient::normalize_words;
+use b39wc::seedqr::{self, Format};
+use b39wc::tokenizer::tokenize;
+use b39wc::{builtin_wordlists, Error, Language, Mnemonic, Wordlist};
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::fs;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("seedqr")
+        .about("Converts a phrase to a SeedQR, or decodes a SeedQR payload or image")
+        .arg(
+            Arg::with_name("input")
+                .required_unless("image")
+                .help("An english phrase to encode, or a standard (digits) or compact (hex) payload to decode"),
+        )
+        .arg(
+            Arg::with_name("image")
+                .short("i")
+                .long("image")
+                .value_name("image")
+                .help("Image file holding a SeedQR to decode")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("compact")
+                .short("c")
+                .long("compact")
+                .help("Encodes the phrase as a compact SeedQR"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .short("f")
+                .long("format")
+                .value_name("format")
+                .help("Rendering of the QR code")
+                .possible_values(&["terminal", "svg", "png"])
+                .default_value("terminal")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .value_name("output")
+                .help("File the SVG or PNG QR code is written to")
+                .required_if("format", "png")
+                .takes_value(true),
+        )
+}
+
+/**
+ * Decodes a payload and checks the phrase against the built-in
+ * dictionaries and its checksum
+ */
+fn decode(payload: &[u8]) -> Result<Mnemonic, Error> {
+    let mnemonic = seedqr::decode_payload(payload)?;
+    mnemonic.check(&builtin_wordlists())?;
+    mnemonic.verify_checksum(&Language::English)?;
+    Ok(mnemonic)
+}
+
+/**
+ * Reads the payload given as argument : digits for a standard
+ * payload, hexadecimal for a compact one
+ */
+fn parse_payload(input: &str) -> Option<Vec<u8>> {
+    match input.bytes().all(|byte| byte.is_ascii_digit()) && input.len().is_multiple_of(4) {
+        true => Some(input.as_bytes().to_vec()),
+        false => from_hex(input),
+    }
+}
+
+/**
+ * Encodes a phrase and renders its QR code.
+ * An SVG document written to the standard output is printed alone.
+ */
+fn encode(mnemonic: &Mnemonic, matches: &ArgMatches) -> Result<(), Error> {
+    let format = match matches.is_present("compact") {
+        true => Format::Compact,
+        false => Format::Standard,
+    };
+    let code = seedqr::qr_code(mnemonic, format)?;
+    let rendering = matches.value_of("format").unwrap_or("terminal");
+    let output = matches.value_of("output");
+
+    if rendering != "svg" || output.is_some() {
+        println!("Standard payload : {}", seedqr::standard_payload(mnemonic)?);
+        println!(
+            "Compact payload : {}",
+            to_hex(&seedqr::compact_payload(mnemonic)?)
+        );
+    }
+    match (rendering, output) {
+        ("svg", Some(path)) => {
+            fs::write(path, seedqr::render_svg(&code))
+                .map_err(|_| Error::FileWrite(path.to_string()))?;
+        }
+        ("svg", None) => println!("{}", seedqr::render_svg(&code)),
+        ("png", Some(path)) => seedqr::save_png(&code, path)?,
+        _ => println!("{}", seedqr::render_terminal(&code)),
+    }
+    if let Some(path) = output {
+        green_ln!("SeedQR written to {}", path);
+    }
+    Ok(())
+}
+
+/**
+ * Runs the seedqr command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let result = match (matches.value_of("image"), matches.value_of("input")) {
+        (Some(path), _) => seedqr::read_image(path).and_then(|payload| decode(&payload)),
+        (None, Some(input)) => match parse_payload(input.trim()) {
+            Some(payload) => decode(&payload),
+            None => {
+                let wordlists: Vec<Box<dyn Wordlist>> = vec![Box::new(Language::English)];
+                let (words, _) = normalize_words(&tokenize(input).words, &wordlists);
+                let mnemonic = Mnemonic::from_words(words);
+                return match encode(&mnemonic, matches) {
+                    Ok(()) => 0,
+                    Err(error) => {
+                        red_ln!("{}", error);
+                        1
+                    }
+                };
+            }
+        },
+        (None, None) => unreachable!("input is required without an image"),
+    };
+
+    match result {
+        Ok(mnemonic) => {
+            green_ln!("SeedQR holds a valid phrase : {}", mnemonic);
+            0
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use super::{read_phrase, report};
+use b39wc::seedxor::{combine, split};
+use b39wc::{Error, Language, Mnemonic};
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("seedxor")
+        .about("Splits a phrase into Seed XOR parts, or combines them")
+        .subcommand(
+            SubCommand::with_name("split")
+                .about("Splits a phrase into parts that XOR together to it")
+                .arg(
+                    Arg::with_name("phrase")
+                        .required(true)
+                        .help("The phrase to split"),
+                )
+                .arg(
+                    Arg::with_name("parts")
+                        .short("n")
+                        .long("parts")
+                        .value_name("parts")
+                        .help("Number of parts")
+                        .default_value("3")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("combine")
+                .about("Combines parts into the original phrase")
+                .arg(
+                    Arg::with_name("parts")
+                        .required(true)
+                        .multiple(true)
+                        .help("The parts to combine, one argument per part"),
+                ),
+        )
+}
+
+fn run_split(matches: &ArgMatches, count: usize) -> Result<(), Error> {
+    let (mnemonic, language) = read_phrase(matches.value_of("phrase").unwrap_or(""))?;
+    let parts = split(&mnemonic, &language, count, &mut rand::thread_rng())?;
+    for (index, part) in parts.iter().enumerate() {
+        green_ln!("Part {} : {}", index + 1, part.to_phrase(&language));
+    }
+    Ok(())
+}
+
+/**
+ * Combines valid parts, which must all be in the same language
+ */
+fn combine_parts(parts: &[(Mnemonic, Language)]) -> Result<(), Error> {
+    let language = parts.first().map(|(_, language)| *language);
+    if parts.iter().any(|(_, other)| Some(*other) != language) {
+        return Err(Error::ShareMismatch("languages".to_string()));
+    }
+
+    let language = language.unwrap_or(Language::English);
+    let mnemonics: Vec<Mnemonic> = parts.iter().map(|(part, _)| part.clone()).collect();
+    let mnemonic = combine(&mnemonics, &language)?;
+    green_ln!("Combined phrase : {}", mnemonic.to_phrase(&language));
+    Ok(())
+}
+
+fn run_combine(matches: &ArgMatches) -> i32 {
+    let mut parts: Vec<(Mnemonic, Language)> = Vec::new();
+    let mut valid = true;
+    for (index, phrase) in matches.values_of("parts").unwrap().enumerate() {
+        match read_phrase(phrase) {
+            Ok((part, language)) => {
+                green_ln!("Part {} is valid ({})", index + 1, language.name());
+                parts.push((part, language));
+            }
+            Err(error) => {
+                red_ln!("Part {} is invalid : {}", index + 1, error);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        return 1;
+    }
+    report(combine_parts(&parts))
+}
+
+/**
+ * Runs the seedxor command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    match matches.subcommand() {
+        ("split", Some(sub_matches)) => match value_t!(sub_matches, "parts", usize) {
+            Ok(count) => report(run_split(sub_matches, count)),
+            Err(error) => {
+                red_ln!("{}", error.message);
+                1
+            }
+        },
+        ("combine", Some(sub_matches)) => run_combine(sub_matches),
+        _ => {
+            println!("{}", matches.usage());
+            1
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use b39wc::encoding::to_hex;
+use b39wc::lenient::normalize_words;
+use b39wc::slip39::{combine, Share, Slip39Wordlist};
+use b39wc::tokenizer::tokenize;
+use b39wc::Wordlist;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("slip39")
+        .about("Checks SLIP-39 shares and recovers their master secret")
+        .arg(
+            Arg::with_name("shares")
+                .required(true)
+                .multiple(true)
+                .help("The shares to check, one argument per share"),
+        )
+        .arg(
+            Arg::with_name("passphrase")
+                .short("p")
+                .long("passphrase")
+                .value_name("passphrase")
+                .help("Passphrase protecting the master secret")
+                .takes_value(true),
+        )
+}
+
+/**
+ * Runs the slip39 command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let wordlists: Vec<Box<dyn Wordlist>> = vec![Box::new(Slip39Wordlist)];
+    let mut shares: Vec<Share> = Vec::new();
+    let mut valid = true;
+
+    for (index, phrase) in matches.values_of("shares").unwrap().enumerate() {
+        let (words, _) = normalize_words(&tokenize(phrase).words, &wordlists);
+        match Share::from_words(&words) {
+            Ok(share) => {
+                green_ln!("Share {} is valid : {}", index + 1, share);
+                shares.push(share);
+            }
+            Err(error) => {
+                red_ln!("Share {} is invalid : {}", index + 1, error);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        return 1;
+    }
+
+    let passphrase = matches.value_of("passphrase").unwrap_or("");
+    match combine(&shares, passphrase.as_bytes()) {
+        Ok(secret) => {
+            green_ln!("Master secret : {}", to_hex(&secret));
+            0
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
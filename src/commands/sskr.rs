@@ -0,0 +1,141 @@
+use super::{read_phrase, report};
+use b39wc::sskr::{combine, split, GroupSpec, Share};
+use b39wc::{Error, Language, Mnemonic};
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::str::FromStr;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("sskr")
+        .about("Splits a phrase into SSKR shares, or recovers it from them")
+        .subcommand(
+            SubCommand::with_name("split")
+                .about("Splits the entropy of a phrase into groups of SSKR shares")
+                .arg(
+                    Arg::with_name("phrase")
+                        .required(true)
+                        .help("The phrase to split"),
+                )
+                .arg(
+                    Arg::with_name("group")
+                        .short("g")
+                        .long("group")
+                        .value_name("M-of-N")
+                        .help("A group of N shares, M of them needed, repeated for each group")
+                        .default_value("2-of-3")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("group-threshold")
+                        .short("t")
+                        .long("group-threshold")
+                        .value_name("threshold")
+                        .help("Number of groups needed to recover the phrase")
+                        .default_value("1")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("combine")
+                .about("Recovers a phrase from SSKR shares written as Bytewords")
+                .arg(
+                    Arg::with_name("shares")
+                        .required(true)
+                        .multiple(true)
+                        .help("The shares to combine, one argument per share"),
+                )
+                .arg(
+                    Arg::with_name("language")
+                        .short("l")
+                        .long("language")
+                        .value_name("language")
+                        .help("Language of the recovered phrase")
+                        .default_value("english")
+                        .takes_value(true),
+                ),
+        )
+}
+
+fn run_split(matches: &ArgMatches, group_threshold: usize) -> Result<(), Error> {
+    let (mnemonic, language) = read_phrase(matches.value_of("phrase").unwrap_or(""))?;
+    let groups = matches
+        .values_of("group")
+        .unwrap()
+        .map(GroupSpec::from_str)
+        .collect::<Result<Vec<GroupSpec>, Error>>()?;
+    let entropy = mnemonic.to_entropy(&language)?;
+
+    let shares = split(&entropy, group_threshold, &groups, &mut rand::thread_rng())?;
+    for (group_index, group) in shares.iter().enumerate() {
+        for (member_index, share) in group.iter().enumerate() {
+            green_ln!(
+                "Group {} share {} : {}",
+                group_index + 1,
+                member_index + 1,
+                share.encode()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_combine(matches: &ArgMatches) -> i32 {
+    let language = match Language::from_str(matches.value_of("language").unwrap_or("english")) {
+        Ok(language) => language,
+        Err(error) => {
+            red_ln!("{}", error);
+            return 1;
+        }
+    };
+
+    let mut shares: Vec<Share> = Vec::new();
+    let mut valid = true;
+    for (index, encoded) in matches.values_of("shares").unwrap().enumerate() {
+        match Share::parse(encoded) {
+            Ok(share) => {
+                green_ln!("Share {} is valid : {}", index + 1, share);
+                shares.push(share);
+            }
+            Err(error) => {
+                red_ln!("Share {} is invalid : {}", index + 1, error);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        return 1;
+    }
+
+    let recovered =
+        combine(&shares).and_then(|entropy| Mnemonic::from_entropy(&entropy, &language));
+    report(recovered.map(|mnemonic| {
+        green_ln!(
+            "Phrase ({}) : {}",
+            language.name(),
+            mnemonic.to_phrase(&language)
+        );
+    }))
+}
+
+/**
+ * Runs the sskr command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    match matches.subcommand() {
+        ("split", Some(sub_matches)) => match value_t!(sub_matches, "group-threshold", usize) {
+            Ok(group_threshold) => report(run_split(sub_matches, group_threshold)),
+            Err(error) => {
+                red_ln!("{}", error.message);
+                1
+            }
+        },
+        ("combine", Some(sub_matches)) => run_combine(sub_matches),
+        _ => {
+            println!("{}", matches.usage());
+            1
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::recover::{
+    checkpoint_args, close_checkpoint, numbers, open_checkpoint, option_values, print_progress,
+    read_oracle, save_progress, Numbers,
+};
+use b39wc::checkpoint::CheckpointFile;
+use b39wc::recovery::{recover, recover_first, Candidates, Scrambled};
+use b39wc::{Error, Language, Mnemonic};
+use clap::{value_t, values_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+use std::str::FromStr;
+
+/**
+ * Options of an unscramble search saved in its checkpoint,
+ * the fixed positions included
+ */
+const SEARCH_OPTIONS: [&str; 7] = [
+    "phrase",
+    "fixed",
+    "target",
+    "path",
+    "gap",
+    "passphrase",
+    "language",
+];
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    let app = SubCommand::with_name("unscramble")
+        .about("Recovers the order of the words of a phrase")
+        .arg(
+            Arg::with_name("phrase")
+                .required(true)
+                .help("All the words of the phrase, in any order"),
+        )
+        .arg(
+            Arg::with_name("fixed")
+                .short("f")
+                .long("fixed")
+                .value_name("position")
+                .help("Position, starting at 1, of a word already in its place")
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("target")
+                .short("t")
+                .long("target")
+                .value_name("target")
+                .help("Master fingerprint, xpub, ypub, zpub or address of the phrase")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("path")
+                .long("path")
+                .value_name("path")
+                .help("Derivation path of the xpub, or of the parent of the addresses")
+                .requires("target")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("gap")
+                .long("gap")
+                .value_name("gap")
+                .help("Number of addresses checked under the path")
+                .default_value("20")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("passphrase")
+                .short("p")
+                .long("passphrase")
+                .value_name("passphrase")
+                .help("Passphrase of the phrase")
+                .requires("target")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("language")
+                .short("l")
+                .long("language")
+                .value_name("language")
+                .help("Language of the phrase")
+                .default_value("english")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .value_name("limit")
+                .help("Number of orders with a valid checksum printed without a target")
+                .default_value("10")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .help("Searches even if the search would take weeks or more"),
+        )
+        .arg(
+            Arg::with_name("threads")
+                .short("j")
+                .long("threads")
+                .value_name("threads")
+                .help("Number of threads, all the cores by default")
+                .takes_value(true),
+        );
+    checkpoint_args(app)
+}
+
+/**
+ * Prints the orders with a valid checksum, up to a limit,
+ * and returns how many were found
+ */
+fn list_orders(
+    scrambled: &Scrambled,
+    language: &Language,
+    limit: usize,
+    threads: usize,
+    checkpoint: &mut Option<CheckpointFile>,
+) -> usize {
+    let mut found = 0;
+    recover(
+        scrambled,
+        language,
+        "",
+        None,
+        checkpoint.as_ref().map_or(0, CheckpointFile::position),
+        threads,
+        &mut |progress, matches| {
+            print_progress(progress);
+            for mnemonic in matches.iter().take(limit - found) {
+                eprintln!();
+                green_ln!("Valid order : {}", mnemonic.to_phrase(language));
+            }
+            if found + matches.len() > limit {
+                // Orders left unprinted are found again when resuming
+                found = limit;
+                return false;
+            }
+            found += matches.len();
+            save_progress(checkpoint, progress) && found < limit
+        },
+    );
+    eprintln!();
+    found
+}
+
+fn search(
+    matches: &ArgMatches,
+    numbers: &Numbers,
+    fixed: &[usize],
+    limit: usize,
+) -> Result<bool, Error> {
+    let language = Language::from_str(matches.value_of("language").unwrap_or("english"))?;
+    let mnemonic = Mnemonic::parse(matches.value_of("phrase").unwrap_or(""));
+    let scrambled = Scrambled::new(&mnemonic, &language, fixed)?;
+    let oracle = read_oracle(matches, numbers)?;
+
+    println!(
+        "Searching {} orders of the words, about {} with a valid checksum",
+        scrambled.count(),
+        scrambled.seeds()
+    );
+    if !scrambled.is_feasible(oracle.is_some()) {
+        if !matches.is_present("force") {
+            red_ln!("This search would take weeks or more, fix more positions or use --force");
+            return Ok(false);
+        }
+        red_ln!("This search would take weeks or more");
+    }
+
+    let mut checkpoint = open_checkpoint(
+        matches,
+        "unscramble",
+        &option_values(matches, &SEARCH_OPTIONS),
+        scrambled.count(),
+    )?;
+    let oracle = match oracle {
+        Some(oracle) => oracle,
+        None => {
+            let found = list_orders(
+                &scrambled,
+                &language,
+                limit,
+                numbers.threads,
+                &mut checkpoint,
+            );
+            close_checkpoint(checkpoint)?;
+            if found == 0 {
+                red_ln!("No order has a valid checksum");
+            }
+            return Ok(found > 0);
+        }
+    };
+    println!("Checking them against the {}", oracle);
+    let found = recover_first(
+        &scrambled,
+        &language,
+        matches.value_of("passphrase").unwrap_or(""),
+        &oracle,
+        checkpoint.as_ref().map_or(0, CheckpointFile::position),
+        numbers.threads,
+        &mut |progress| {
+            print_progress(progress);
+            save_progress(&mut checkpoint, progress)
+        },
+    );
+    eprintln!();
+    close_checkpoint(checkpoint)?;
+
+    match found {
+        Some(mnemonic) => {
+            green_ln!("Match : {}", mnemonic.to_phrase(&language));
+            Ok(true)
+        }
+        None => {
+            red_ln!("No order matches the target");
+            Ok(false)
+        }
+    }
+}
+
+/**
+ * Runs the unscramble command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    let options = numbers(matches).and_then(|numbers| {
+        let fixed = match matches.is_present("fixed") {
+            true => values_t!(matches, "fixed", usize)?,
+            false => vec![],
+        };
+        Ok((numbers, fixed, value_t!(matches, "limit", usize)?))
+    });
+    let (numbers, fixed, limit) = match options {
+        Ok(options) => options,
+        Err(error) => {
+            red_ln!("{}", error.message);
+            return 1;
+        }
+    };
+
+    match search(matches, &numbers, &fixed, limit.max(1)) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(error) => {
+            red_ln!("{}", error);
+            1
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::{read_phrase, report};
+use b39wc::ur::{self, UrType};
+use b39wc::Error;
+use clap::{value_t, App, Arg, ArgMatches, SubCommand};
+use colour::green_ln;
+use colour::red_ln;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("ur")
+        .about("Converts a phrase to crypto-seed or crypto-bip39 URs, or decodes them")
+        .subcommand(
+            SubCommand::with_name("encode")
+                .about("Encodes a phrase as a UR, or as the parts of an animated multipart UR")
+                .arg(
+                    Arg::with_name("phrase")
+                        .required(true)
+                        .help("The phrase to encode"),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .value_name("type")
+                        .help("UR type : the entropy (seed) or the words (bip39) of the phrase")
+                        .possible_values(&["seed", "bip39"])
+                        .default_value("seed")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max-fragment")
+                        .short("m")
+                        .long("max-fragment")
+                        .value_name("bytes")
+                        .help("Splits the UR into parts holding fragments of at most this size")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("parts")
+                        .short("n")
+                        .long("parts")
+                        .value_name("parts")
+                        .help("Number of parts printed, at least one per fragment")
+                        .requires("max-fragment")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("decode")
+                .about("Decodes a UR, or the parts of a multipart UR, into its phrase")
+                .arg(
+                    Arg::with_name("urs")
+                        .required(true)
+                        .multiple(true)
+                        .help("The UR, or the parts of a multipart UR in any order"),
+                ),
+        )
+}
+
+/**
+ * Maximum length of the fragments and number of parts of a
+ * multipart UR, if one is asked for
+ */
+fn fragments(matches: &ArgMatches) -> Result<Option<(usize, usize)>, clap::Error> {
+    if !matches.is_present("max-fragment") {
+        return Ok(None);
+    }
+    let parts = match matches.is_present("parts") {
+        true => value_t!(matches, "parts", usize)?,
+        false => 0,
+    };
+    Ok(Some((value_t!(matches, "max-fragment", usize)?, parts)))
+}
+
+fn run_encode(matches: &ArgMatches, fragments: Option<(usize, usize)>) -> Result<(), Error> {
+    let (mnemonic, language) = read_phrase(matches.value_of("phrase").unwrap_or(""))?;
+    let ur_type = match matches.value_of("type") {
+        Some("bip39") => UrType::Bip39,
+        _ => UrType::Seed,
+    };
+    let message = ur::to_message(&mnemonic, language, ur_type)?;
+
+    let urs = match fragments {
+        Some((max_fragment, parts)) => {
+            ur::encode_parts(ur_type.name(), &message, max_fragment, parts)
+        }
+        None => vec![ur::encode(ur_type.name(), &message)],
+    };
+    for ur in urs {
+        green_ln!("{}", ur);
+    }
+    Ok(())
+}
+
+fn run_decode(matches: &ArgMatches) -> Result<(), Error> {
+    let urs: Vec<&str> = matches.values_of("urs").unwrap().collect();
+    let (mnemonic, language) = ur::decode_phrase(&urs)?;
+    green_ln!(
+        "UR holds a valid phrase ({}) : {}",
+        language.name(),
+        mnemonic.to_phrase(&language)
+    );
+    Ok(())
+}
+
+/**
+ * Runs the ur command
+ */
+pub fn run(matches: &ArgMatches) -> i32 {
+    match matches.subcommand() {
+        ("encode", Some(sub_matches)) => match fragments(sub_matches) {
+            Ok(fragments) => report(run_encode(sub_matches, fragments)),
+            Err(error) => {
+                red_ln!("{}", error.message);
+                1
+            }
+        },
+        ("decode", Some(sub_matches)) => report(run_decode(sub_matches)),
+        _ => {
+            println!("{}", matches.usage());
+            1
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::language::Language;
+use crate::wordlist::Wordlist;
+use std::fmt;
+
+/**
+ * Characters looking like latin letters, with the letter they
+ * can be mistaken for and their Unicode name
+ */
+const CONFUSABLES: [(char, char, &str); 63] = [
+    ('\u{0430}', 'a', "CYRILLIC SMALL LETTER A"),
+    ('\u{0435}', 'e', "CYRILLIC SMALL LETTER IE"),
+    ('\u{043E}', 'o', "CYRILLIC SMALL LETTER O"),
+    ('\u{0440}', 'p', "CYRILLIC SMALL LETTER ER"),
+    ('\u{0441}', 'c', "CYRILLIC SMALL LETTER ES"),
+    ('\u{0443}', 'y', "CYRILLIC SMALL LETTER U"),
+    ('\u{0445}', 'x', "CYRILLIC SMALL LETTER HA"),
+    ('\u{0455}', 's', "CYRILLIC SMALL LETTER DZE"),
+    (
+        '\u{0456}',
+        'i',
+        "CYRILLIC SMALL LETTER BYELORUSSIAN-UKRAINIAN I",
+    ),
+    ('\u{0458}', 'j', "CYRILLIC SMALL LETTER JE"),
+    ('\u{0501}', 'd', "CYRILLIC SMALL LETTER KOMI DE"),
+    ('\u{04BB}', 'h', "CYRILLIC SMALL LETTER SHHA"),
+    ('\u{04CF}', 'l', "CYRILLIC SMALL LETTER PALOCHKA"),
+    ('\u{051B}', 'q', "CYRILLIC SMALL LETTER QA"),
+    ('\u{051D}', 'w', "CYRILLIC SMALL LETTER WE"),
+    ('\u{0475}', 'v', "CYRILLIC SMALL LETTER IZHITSA"),
+    ('\u{04AF}', 'y', "CYRILLIC SMALL LETTER STRAIGHT U"),
+    ('\u{0410}', 'A', "CYRILLIC CAPITAL LETTER A"),
+    ('\u{0412}', 'B', "CYRILLIC CAPITAL LETTER VE"),
+    ('\u{0415}', 'E', "CYRILLIC CAPITAL LETTER IE"),
+    ('\u{041A}', 'K', "CYRILLIC CAPITAL LETTER KA"),
+    ('\u{041C}', 'M', "CYRILLIC CAPITAL LETTER EM"),
+    ('\u{041D}', 'H', "CYRILLIC CAPITAL LETTER EN"),
+    ('\u{041E}', 'O', "CYRILLIC CAPITAL LETTER O"),
+    ('\u{0420}', 'P', "CYRILLIC CAPITAL LETTER ER"),
+    ('\u{0421}', 'C', "CYRILLIC CAPITAL LETTER ES"),
+    ('\u{0422}', 'T', "CYRILLIC CAPITAL LETTER TE"),
+    ('\u{0425}', 'X', "CYRILLIC CAPITAL LETTER HA"),
+    (
+        '\u{0406}',
+        'I',
+        "CYRILLIC CAPITAL LETTER BYELORUSSIAN-UKRAINIAN I",
+    ),
+    ('\u{0408}', 'J', "CYRILLIC CAPITAL LETTER JE"),
+    ('\u{0405}', 'S', "CYRILLIC CAPITAL LETTER DZE"),
+    ('\u{03B1}', 'a', "GREEK SMALL LETTER ALPHA"),
+    ('\u{03BF}', 'o', "GREEK SMALL LETTER OMICRON"),
+    ('\u{03BD}', 'v', "GREEK SMALL LETTER NU"),
+    ('\u{03B9}', 'i', "GREEK SMALL LETTER IOTA"),
+    ('\u{03BA}', 'k', "GREEK SMALL LETTER KAPPA"),
+    ('\u{03C1}', 'p', "GREEK SMALL LETTER RHO"),
+    ('\u{03C5}', 'u', "GREEK SMALL LETTER UPSILON"),
+    ('\u{03C7}', 'x', "GREEK SMALL LETTER CHI"),
+    ('\u{03B3}', 'y', "GREEK SMALL LETTER GAMMA"),
+    ('\u{0391}', 'A', "GREEK CAPITAL LETTER ALPHA"),
+    ('\u{0392}', 'B', "GREEK CAPITAL LETTER BETA"),
+    ('\u{0395}', 'E', "GREEK CAPITAL LETTER EPSILON"),
+    ('\u{0396}', 'Z', "GREEK CAPITAL LETTER ZETA"),
+    ('\u{0397}', 'H', "GREEK CAPITAL LETTER ETA"),
+    ('\u{0399}', 'I', "GREEK CAPITAL LETTER IOTA"),
+    ('\u{039A}', 'K', "GREEK CAPITAL LETTER KAPPA"),
+    ('\u{039C}', 'M', "GREEK CAPITAL LETTER MU"),
+    ('\u{039D}', 'N', "GREEK CAPITAL LETTER NU"),
+    ('\u{039F}', 'O', "GREEK CAPITAL LETTER OMICRON"),
+    ('\u{03A1}', 'P', "GREEK CAPITAL LETTER RHO"),
+    ('\u{03A4}', 'T', "GREEK CAPITAL LETTER TAU"),
+    ('\u{03A5}', 'Y', "GREEK CAPITAL LETTER UPSILON"),
+    ('\u{03A7}', 'X', "GREEK CAPITAL LETTER CHI"),
+    ('\u{0131}', 'i', "LATIN SMALL LETTER DOTLESS I"),
+    ('\u{0237}', 'j', "LATIN SMALL LETTER DOTLESS J"),
+    ('\u{0251}', 'a', "LATIN SMALL LETTER ALPHA"),
+    ('\u{0261}', 'g', "LATIN SMALL LETTER SCRIPT G"),
+    ('\u{0269}', 'i', "LATIN SMALL LETTER IOTA"),
+    ('\u{0585}', 'o', "ARMENIAN SMALL LETTER OH"),
+    ('\u{057D}', 'u', "ARMENIAN SMALL LETTER SEH"),
+    ('\u{0570}', 'h', "ARMENIAN SMALL LETTER HO"),
+    ('\u{0578}', 'n', "ARMENIAN SMALL LETTER VO"),
+];
+
+/**
+ * Returns the latin letter a character can be mistaken for, and its Unicode name
+ */
+pub fn confusable(character: char) -> Option<(char, &'static str)> {
+    CONFUSABLES
+        .iter()
+        .find(|(c, _, _)| *c == character)
+        .map(|(_, replacement, name)| (*replacement, *name))
+}
+
+/**
+ * Writing systems the letters of a word can belong to
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hangul,
+    Japanese,
+    Other,
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Script::Latin => "latin",
+            Script::Greek => "greek",
+            Script::Cyrillic => "cyrillic",
+            Script::Armenian => "armenian",
+            Script::Hangul => "hangul",
+            Script::Japanese => "han/kana",
+            Script::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/**
+ * Returns the script of a letter.
+ * Digits, punctuation and combining marks don't belong to a script.
+ * Han ideographs and kanas are grouped as they are written together.
+ */
+pub fn script(character: char) -> Option<Script> {
+    let code = character as u32;
+    match code {
+        0x0300..=0x036F | 0x3099..=0x309C => None,
+        _ if !character.is_alphabetic() => None,
+        0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x02AF | 0x1E00..=0x1EFF => {
+            Some(Script::Latin)
+        }
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Some(Script::Greek),
+        0x0400..=0x052F => Some(Script::Cyrillic),
+        0x0530..=0x058F => Some(Script::Armenian),
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Some(Script::Hangul),
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF => Some(Script::Japanese),
+        _ => Some(Script::Other),
+    }
+}
+
+/**
+ * A character of a word looking like a latin letter.
+ * Position is the index of the character in the word, starting at 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Homoglyph {
+    pub character: char,
+    pub position: usize,
+    pub replacement: char,
+}
+
+impl fmt::Display for Homoglyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = confusable(self.character)
+            .map(|(_, name)| name)
+            .unwrap_or("UNKNOWN CHARACTER");
+        write!(
+            f,
+            "U+{:04X} {} at position {} looks like '{}'",
+            self.character as u32, name, self.position, self.replacement
+        )
+    }
+}
+
+/**
+ * Report of a word that could have been altered to look like another one
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suspicion {
+    pub word: String,
+    pub scripts: Vec<Script>,
+    pub homoglyphs: Vec<Homoglyph>,
+}
+
+impl Suspicion {
+    /**
+     * Checks if the word mixes letters of several scripts
+     */
+    pub fn is_mixed_script(&self) -> bool {
+        self.scripts.len() > 1
+    }
+
+    /**
+     * Returns the word with every homoglyph replaced by the letter it looks like
+     */
+    pub fn skeleton(&self) -> String {
+        skeleton(&self.word)
+    }
+
+    /**
+     * Looks for the word the suspicious one was made to look like
+     * in the built-in dictionaries
+     */
+    pub fn canonical(&self) -> Option<(&'static str, Language)> {
+        let skeleton = self.skeleton().to_lowercase();
+        Language::ALL.iter().find_map(|&language| {
+            Wordlist::index_of(&language, &skeleton)
+                .map(|index| (language.words()[index], language))
+        })
+    }
+}
+
+/**
+ * Replaces every character looking like a latin letter by that letter
+ */
+pub fn skeleton(word: &str) -> String {
+    word.chars()
+        .map(|c| {
+            confusable(c)
+                .map(|(replacement, _)| replacement)
+                .unwrap_or(c)
+        })
+        .collect()
+}
+
+/**
+ * Inspects a word for letters of mixed scripts and characters
+ * looking like latin letters.
+ * Returns None if nothing suspicious was found.
+ */
+pub fn inspect(word: &str) -> Option<Suspicion> {
+    let mut scripts: Vec<Script> = word.chars().filter_map(script).collect();
+    scripts.sort();
+    scripts.dedup();
+
+    let homoglyphs: Vec<Homoglyph> = word
+        .chars()
+        .enumerate()
+        .filter_map(|(index, character)| {
+            confusable(character).map(|(replacement, _)| Homoglyph {
+                character,
+                position: index + 1,
+                replacement,
+            })
+        })
+        .collect();
+
+    if scripts.len() <= 1 && homoglyphs.is_empty() {
+        return None;
+    }
+
+    Some(Suspicion {
+        word: word.to_string(),
+        scripts,
+        homoglyphs,
+    })
+}
+
+#[test]
+fn test_script() {
+    assert_eq!(Some(Script::Latin), script('a'));
+    assert_eq!(Some(Script::Cyrillic), script('\u{430}'));
+    assert_eq!(Some(Script::Japanese), script('あ'));
+    assert_eq!(Some(Script::Hangul), script('가'));
+    assert_eq!(None, script('\u{301}'));
+    assert_eq!(None, script('1'));
+}
+
+#[test]
+fn test_inspect() {
+    assert_eq!(None, inspect("abandon"));
+    assert_eq!(None, inspect("ve\u{301}lo"));
+
+    // "abandon" with a cyrillic "а"
+    let suspicion = inspect("\u{430}bandon").unwrap();
+    assert!(suspicion.is_mixed_script());
+    assert_eq!(vec![Script::Latin, Script::Cyrillic], suspicion.scripts);
+    assert_eq!(
+        vec![Homoglyph {
+            character: '\u{430}',
+            position: 1,
+            replacement: 'a'
+        }],
+        suspicion.homoglyphs
+    );
+    assert_eq!(
+        "U+0430 CYRILLIC SMALL LETTER A at position 1 looks like 'a'",
+        suspicion.homoglyphs[0].to_string()
+    );
+    assert_eq!("abandon", suspicion.skeleton());
+    assert_eq!(Some(("abandon", Language::English)), suspicion.canonical());
+}
+
+#[test]
+fn test_inspect_single_script() {
+    // "copy" written with cyrillic letters only
+    let suspicion = inspect("\u{441}\u{43e}\u{440}\u{443}").unwrap();
+    assert!(!suspicion.is_mixed_script());
+    assert_eq!(4, suspicion.homoglyphs.len());
+    assert_eq!("copy", suspicion.skeleton());
+    assert_eq!(Some(("copy", Language::English)), suspicion.canonical());
+}
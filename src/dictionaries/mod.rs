@@ -1,23 +1,28 @@
 mod chinese_simplified;
 mod chinese_traditional;
 mod czech;
+mod electrum_old;
 mod english;
 mod french;
 mod italian;
 mod japanese;
 mod korean;
+mod monero_english;
 mod portuguese;
+mod slip39;
 mod spanish;
 pub mod langs {
     use super::chinese_simplified::CHINESE_SIMPLIFIED_WORDS;
     use super::chinese_traditional::CHINESE_TRADITIONAL_WORDS;
     use super::czech::CZECH_WORDS;
+    use super::electrum_old::ELECTRUM_OLD_WORDS;
     use super::english::ENGLISH_WORDS;
     use super::french::FRENCH_WORDS;
     use super::italian::ITALIAN_WORDS;
     use super::japanese::JAPANESE_WORDS;
     use super::korean::KOREAN_WORDS;
     use super::portuguese::PORTUGUESE_WORDS;
+    use super::slip39::SLIP39_WORDS;
     use super::spanish::SPANISH_WORDS;
 
     pub static CZECH: [&str; 2048] = CZECH_WORDS;
@@ -30,4 +35,11 @@ pub mod langs {
     pub static KOREAN: [&str; 2048] = KOREAN_WORDS;
     pub static CHINESE_TRADITIONAL: [&str; 2048] = CHINESE_TRADITIONAL_WORDS;
     pub static CHINESE_SIMPLIFIED: [&str; 2048] = CHINESE_SIMPLIFIED_WORDS;
+    pub static ELECTRUM_OLD: [&str; 1626] = ELECTRUM_OLD_WORDS;
+    pub static SLIP39: [&str; 1024] = SLIP39_WORDS;
+}
+pub mod monero {
+    use super::monero_english::MONERO_ENGLISH_WORDS;
+
+    pub static ENGLISH: [&str; 1626] = MONERO_ENGLISH_WORDS;
 }
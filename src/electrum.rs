@@ -0,0 +1,308 @@
+use crate::dictionaries::langs::ELECTRUM_OLD;
+use crate::encoding::to_hex;
+use crate::error::Error;
+use crate::language::Language;
+use crate::wordlist::Wordlist;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * Ranges of CJK characters, between which Electrum removes whitespace
+ */
+const CJK_RANGES: [(u32, u32); 23] = [
+    (0x1100, 0x11FF),
+    (0x2E80, 0x2EFF),
+    (0x2F00, 0x2FDF),
+    (0x2FF0, 0x2FFF),
+    (0x3000, 0x303F),
+    (0x3040, 0x309F),
+    (0x30A0, 0x30FF),
+    (0x3130, 0x318F),
+    (0x3190, 0x319F),
+    (0x31C0, 0x31EF),
+    (0x31F0, 0x31FF),
+    (0x3200, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7AF),
+    (0xD7B0, 0xD7FF),
+    (0xF900, 0xFAFF),
+    (0xFE30, 0xFE4F),
+    (0xFF00, 0xFFEF),
+    (0x1B000, 0x1B0FF),
+    (0x20000, 0x2A6DF),
+    (0x2A700, 0x2FA1F),
+];
+
+/**
+ * Kinds of seeds created by the Electrum wallet.
+ * They use the BIP-39 english words, but the version of the seed
+ * is held by the hash of the phrase instead of a checksum.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedType {
+    Standard,
+    Segwit,
+    TwoFactor,
+    TwoFactorSegwit,
+}
+
+impl SeedType {
+    pub const ALL: [SeedType; 4] = [
+        SeedType::Standard,
+        SeedType::Segwit,
+        SeedType::TwoFactor,
+        SeedType::TwoFactorSegwit,
+    ];
+
+    /**
+     * Returns the hexadecimal prefix of the "Seed version" HMAC
+     * of phrases of this type
+     */
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            SeedType::Standard => "01",
+            SeedType::Segwit => "100",
+            SeedType::TwoFactor => "101",
+            SeedType::TwoFactorSegwit => "102",
+        }
+    }
+
+    /**
+     * Returns the numbers of words of phrases of this type.
+     * Two-factor seeds of old Electrum versions had 13 words.
+     */
+    pub fn word_counts(&self) -> &'static [usize] {
+        match self {
+            SeedType::Standard | SeedType::Segwit => &[12, 24],
+            SeedType::TwoFactor | SeedType::TwoFactorSegwit => &[12, 13, 24],
+        }
+    }
+}
+
+impl fmt::Display for SeedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SeedType::Standard => "standard",
+            SeedType::Segwit => "segwit",
+            SeedType::TwoFactor => "2fa",
+            SeedType::TwoFactorSegwit => "2fa-segwit",
+        };
+        f.write_str(name)
+    }
+}
+
+fn is_cjk(character: char) -> bool {
+    let code = character as u32;
+    CJK_RANGES
+        .iter()
+        .any(|&(start, end)| code >= start && code <= end)
+}
+
+/**
+ * Normalizes a phrase as Electrum does before hashing it :
+ * NFKD normalized, lowercased, without accents, with single spaces
+ * between words and no space between CJK characters
+ */
+pub fn normalize_text(phrase: &str) -> String {
+    let lowercase = phrase.nfkd().collect::<String>().to_lowercase();
+    let stripped: String = lowercase
+        .chars()
+        .filter(|&c| canonical_combining_class(c) == 0)
+        .collect();
+    let spaced: Vec<char> = stripped
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .collect();
+
+    spaced
+        .iter()
+        .enumerate()
+        .filter(|&(index, &c)| {
+            !(c == ' ' && index > 0 && is_cjk(spaced[index - 1]) && is_cjk(spaced[index + 1]))
+        })
+        .map(|(_, &c)| c)
+        .collect()
+}
+
+/**
+ * Returns the type of Electrum seed a phrase is, if any
+ */
+pub fn seed_type(phrase: &str) -> Option<SeedType> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"Seed version").expect("any key length is valid");
+    mac.update(normalize_text(phrase).as_bytes());
+    let hash = to_hex(&mac.finalize().into_bytes());
+
+    SeedType::ALL
+        .iter()
+        .copied()
+        .find(|seed_type| hash.starts_with(seed_type.prefix()))
+}
+
+/**
+ * The dictionary of the seeds created by Electrum before version 2.0.
+ * Its 1626 words encode the seed in base 1626 instead of
+ * holding a BIP-39 checksum.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElectrumOldWordlist;
+
+impl ElectrumOldWordlist {
+    pub const SIZE: usize = 1626;
+}
+
+impl Wordlist for ElectrumOldWordlist {
+    fn name(&self) -> String {
+        "electrum_old".to_string()
+    }
+
+    fn language(&self) -> Option<Language> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        ELECTRUM_OLD.len()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        ELECTRUM_OLD.get(index).copied()
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        old_indexes().get(word).copied()
+    }
+}
+
+/**
+ * Returns the index of every word of the old Electrum dictionary,
+ * built on first use
+ */
+fn old_indexes() -> &'static HashMap<&'static str, usize> {
+    static INDEXES: OnceLock<HashMap<&'static str, usize>> = OnceLock::new();
+    INDEXES.get_or_init(|| {
+        ELECTRUM_OLD
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| (word, index))
+            .collect()
+    })
+}
+
+/**
+ * Decodes the seed held by an old Electrum phrase of 12 or 24 words.
+ * Every three words encode four bytes of the seed.
+ */
+pub fn decode_old_seed(words: &[String]) -> Result<Vec<u8>, Error> {
+    if words.len() != 12 && words.len() != 24 {
+        return Err(Error::OldSeedLength(words.len()));
+    }
+
+    let size = ElectrumOldWordlist::SIZE as u64;
+    let mut seed = Vec::with_capacity(words.len() / 3 * 4);
+    for triple in words.chunks(3) {
+        let mut indexes = [0u64; 3];
+        for (index, word) in indexes.iter_mut().zip(triple) {
+            *index = ElectrumOldWordlist
+                .index_of(word)
+                .ok_or_else(|| Error::UnknownWord(word.clone()))? as u64;
+        }
+
+        let value = indexes[0]
+            + size * ((indexes[1] + size - indexes[0]) % size)
+            + size * size * ((indexes[2] + size - indexes[1]) % size);
+        if value > u32::MAX as u64 {
+            return Err(Error::OldSeedOverflow(triple.join(" ")));
+        }
+        seed.extend_from_slice(&(value as u32).to_be_bytes());
+    }
+
+    Ok(seed)
+}
+
+/**
+ * Encodes a seed of 16 or 32 bytes as an old Electrum phrase
+ */
+pub fn encode_old_seed(seed: &[u8]) -> Vec<&'static str> {
+    let size = ElectrumOldWordlist::SIZE as u32;
+    let mut words = Vec::with_capacity(seed.len() / 4 * 3);
+    for chunk in seed.chunks(4) {
+        let mut bytes = [0u8; 4];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from_be_bytes(bytes);
+
+        let first = value % size;
+        let second = (value / size + first) % size;
+        let third = (value / size / size + second) % size;
+        words.extend(
+            [first, second, third]
+                .iter()
+                .map(|&index| ELECTRUM_OLD[index as usize]),
+        );
+    }
+    words
+}
+
+#[test]
+fn test_normalize_text() {
+    assert_eq!("abc def", normalize_text("  ABC \t déf "));
+    assert_eq!(
+        "あいこくしんあいさつ",
+        normalize_text("あいこくしん\u{3000}あいさつ")
+    );
+}
+
+#[test]
+fn test_seed_type() {
+    assert_eq!(
+        Some(SeedType::Standard),
+        seed_type("cycle rocket west magnet parrot shuffle foot correct salt library feed song")
+    );
+    assert_eq!(
+        Some(SeedType::Segwit),
+        seed_type("bitter grass shiver impose acquire brush forget axis eager alone wine silver")
+    );
+    assert_eq!(
+        Some(SeedType::TwoFactor),
+        seed_type("kiss live scene rude gate step hip quarter bunker oxygen motor glove")
+    );
+    assert_eq!(
+        Some(SeedType::TwoFactorSegwit),
+        seed_type("hungry sword tuna flat critic fiction ready until output dance profit remind")
+    );
+    assert_eq!(
+        None,
+        seed_type("legal winner thank year wave sausage worth useful legal winner thank yellow")
+    );
+}
+
+#[test]
+fn test_old_seed() {
+    let phrase = "hardly point goal hallway patience key stone difference ready caught listen fact";
+    let words: Vec<String> = phrase.split(' ').map(String::from).collect();
+    let seed = decode_old_seed(&words).unwrap();
+    assert_eq!("8edad31a95e7d59f8837667510d75a4d", to_hex(&seed));
+    assert_eq!(words, encode_old_seed(&seed));
+
+    assert_eq!(Err(Error::OldSeedLength(3)), decode_old_seed(&words[..3]));
+    assert_eq!(
+        Err(Error::UnknownWord("erupt".to_string())),
+        decode_old_seed(&[&["erupt".to_string()], &words[1..]].concat())
+    );
+    // The largest value three words can encode does not fit in four bytes
+    let overflow: Vec<String> = ["like", "weary", "unseen"]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+    assert_eq!(
+        Err(Error::OldSeedOverflow("like weary unseen".to_string())),
+        decode_old_seed(&[&overflow[..], &words[3..]].concat())
+    );
+}
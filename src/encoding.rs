@@ -0,0 +1,46 @@
+/**
+ * Formats bytes as lowercase hexadecimal
+ */
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/**
+ * Parses hexadecimal bytes, returns None if a character is not hexadecimal
+ */
+pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/**
+ * Computes the CRC-32 (ISO-HDLC) checksum of some bytes
+ */
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ if crc & 1 == 1 { 0xEDB8_8320 } else { 0 };
+        }
+    }
+    !crc
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!("00ff7a", to_hex(&[0, 255, 122]));
+    assert_eq!(Some(vec![0, 255, 122]), from_hex("00FF7a"));
+    assert_eq!(None, from_hex("0g"));
+    assert_eq!(None, from_hex("012"));
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+}
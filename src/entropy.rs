@@ -0,0 +1,248 @@
+use crate::error::Error;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+/**
+ * Physical sources of entropy.
+ * Dice rolls and cards are hashed with SHA-256, as Coldcard and
+ * SeedSigner do with dice rolls, while coin flips are used as they are,
+ * one bit per flip.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Dice,
+    Coins,
+    Cards,
+}
+
+impl Source {
+    pub const ALL: [Source; 3] = [Source::Dice, Source::Coins, Source::Cards];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Source::Dice => "dice",
+            Source::Coins => "coins",
+            Source::Cards => "cards",
+        }
+    }
+
+    /**
+     * Name of one symbol of the source
+     */
+    pub fn symbol_name(self) -> &'static str {
+        match self {
+            Source::Dice => "dice roll",
+            Source::Coins => "coin flip",
+            Source::Cards => "card",
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Source {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Source, Error> {
+        let lowercase = name.trim().to_lowercase();
+        Source::ALL
+            .iter()
+            .find(|source| source.name() == lowercase)
+            .copied()
+            .ok_or_else(|| Error::UnknownEntropySource(name.to_string()))
+    }
+}
+
+/**
+ * Symbols read from a source, normalized : dice rolls as digits 1 to 6,
+ * coin flips as 1 for heads and 0 for tails, cards as their rank
+ * (a, 2 to 9, t, j, q, k) followed by their suit (c, d, h, s).
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rolls {
+    pub source: Source,
+    symbols: Vec<String>,
+}
+
+const RANKS: &str = "a23456789tjqk";
+const SUITS: &str = "cdhs";
+const DECK_SIZE: usize = 52;
+
+/**
+ * Reads a card written as its rank and suit, 10 being accepted for t
+ */
+fn read_card(card: &str) -> Option<String> {
+    let lowercase = card.to_lowercase();
+    let card = lowercase.replacen("10", "t", 1);
+    let mut chars = card.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(rank), Some(suit), None) if RANKS.contains(rank) && SUITS.contains(suit) => {
+            Some(format!("{}{}", rank, suit))
+        }
+        _ => None,
+    }
+}
+
+impl Rolls {
+    /**
+     * Reads the symbols of a source. Dice rolls and coin flips are single
+     * characters, which may be separated or not, while cards are separated
+     * by spaces or commas and must all be distinct.
+     */
+    pub fn parse(source: Source, input: &str) -> Result<Rolls, Error> {
+        let invalid = |symbol: &str| Error::InvalidEntropySymbol {
+            kind: source.symbol_name().to_string(),
+            symbol: symbol.to_string(),
+        };
+        let separator = |c: char| c.is_whitespace() || c == ',' || c == ';';
+
+        let symbols: Vec<String> = match source {
+            Source::Dice => input
+                .chars()
+                .filter(|c| !separator(*c))
+                .map(|c| match c {
+                    '1'..='6' => Ok(c.to_string()),
+                    _ => Err(invalid(&c.to_string())),
+                })
+                .collect::<Result<_, _>>()?,
+            Source::Coins => input
+                .chars()
+                .filter(|c| !separator(*c))
+                .map(|c| match c.to_ascii_lowercase() {
+                    'h' | '1' => Ok("1".to_string()),
+                    't' | '0' => Ok("0".to_string()),
+                    _ => Err(invalid(&c.to_string())),
+                })
+                .collect::<Result<_, _>>()?,
+            Source::Cards => {
+                let mut cards: Vec<String> = Vec::new();
+                for word in input.split(separator).filter(|word| !word.is_empty()) {
+                    let card = read_card(word).ok_or_else(|| invalid(word))?;
+                    if cards.contains(&card) {
+                        return Err(Error::DuplicateCard(word.to_string()));
+                    }
+                    cards.push(card);
+                }
+                cards
+            }
+        };
+        Ok(Rolls { source, symbols })
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /**
+     * Returns the bits of entropy of the symbols : log2(6) per dice roll,
+     * one per coin flip, and log2 of the number of remaining cards
+     * for each card drawn from the deck.
+     */
+    pub fn bits(&self) -> f64 {
+        let count = self.symbols.len();
+        match self.source {
+            Source::Dice => count as f64 * 6f64.log2(),
+            Source::Coins => count as f64,
+            Source::Cards => (0..count.min(DECK_SIZE))
+                .map(|drawn| ((DECK_SIZE - drawn) as f64).log2())
+                .sum(),
+        }
+    }
+
+    /**
+     * Returns the entropy of a phrase of some words, once enough bits,
+     * rounded to the nearest bit, were provided. That's 50 dice rolls for
+     * 12 words and 99 for 24 words, as Coldcard and SeedSigner ask.
+     */
+    pub fn entropy(&self, words: usize) -> Result<Vec<u8>, Error> {
+        let needed = words * 32 / 3;
+        let provided = self.bits().round() as usize;
+        if provided < needed {
+            return Err(Error::NotEnoughEntropy { provided, needed });
+        }
+
+        let string: String = self.symbols.concat();
+        match self.source {
+            Source::Coins => Ok(string
+                .as_bytes()
+                .chunks(8)
+                .take(needed / 8)
+                .map(|bits| bits.iter().fold(0, |byte, bit| (byte << 1) | (bit - b'0')))
+                .collect()),
+            _ => Ok(Sha256::digest(string.as_bytes())[..needed / 8].to_vec()),
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let rolls = Rolls::parse(Source::Dice, "1 2 3, 456").unwrap();
+    assert_eq!(6, rolls.len());
+    assert_eq!(
+        Err(Error::InvalidEntropySymbol {
+            kind: "dice roll".to_string(),
+            symbol: "7".to_string()
+        }),
+        Rolls::parse(Source::Dice, "1237")
+    );
+
+    let flips = Rolls::parse(Source::Coins, "HTht 10").unwrap();
+    assert_eq!(vec!["1", "0", "1", "0", "1", "0"], flips.symbols);
+
+    let cards = Rolls::parse(Source::Cards, "AS 10h, td 2C").unwrap();
+    assert_eq!(vec!["as", "th", "td", "2c"], cards.symbols);
+    assert_eq!(
+        Err(Error::DuplicateCard("TH".to_string())),
+        Rolls::parse(Source::Cards, "10h TH")
+    );
+    assert!(Rolls::parse(Source::Cards, "1h").is_err());
+}
+
+#[test]
+fn test_bits() {
+    let dice = |count: usize| Rolls::parse(Source::Dice, &"3".repeat(count)).unwrap();
+    assert_eq!(
+        Err(Error::NotEnoughEntropy {
+            provided: 127,
+            needed: 128
+        }),
+        dice(49).entropy(12)
+    );
+    assert!(dice(50).entropy(12).is_ok());
+    assert!(dice(98).entropy(24).is_err());
+    assert!(dice(99).entropy(24).is_ok());
+
+    let deck: Vec<String> = RANKS
+        .chars()
+        .flat_map(|rank| SUITS.chars().map(move |suit| format!("{}{}", rank, suit)))
+        .collect();
+    let cards = Rolls::parse(Source::Cards, &deck.join(" ")).unwrap();
+    // log2(52!)
+    assert_eq!(226, cards.bits().round() as usize);
+    assert!(cards.entropy(21).is_ok());
+    assert!(cards.entropy(24).is_err());
+}
+
+#[test]
+fn test_entropy() {
+    use crate::encoding::to_hex;
+
+    // SHA-256 of the rolls written as digits
+    let rolls = Rolls::parse(Source::Dice, &"123456".repeat(9)).unwrap();
+    assert_eq!(
+        "edceb2d86ed94b3b67b707e8721ca00f",
+        to_hex(&rolls.entropy(12).unwrap())
+    );
+
+    let flips = Rolls::parse(Source::Coins, &"hhhhtttt".repeat(17)).unwrap();
+    assert_eq!(vec![0xF0; 16], flips.entropy(12).unwrap());
+}
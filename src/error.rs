@@ -0,0 +1,402 @@
+use std::fmt;
+
+/**
+ * Errors returned by the b39wc library.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The phrase does not have the expected number of words
+    WordCount { expected: usize, found: usize },
+    /// A dictionary file could not be read
+    DictionaryLoad(String),
+    /// One or many words were not found in the provided wordlists
+    MissingWords(Vec<String>),
+    /// The language name does not match any built-in dictionary
+    UnknownLanguage(String),
+    /// A word is not part of the dictionary used for decoding
+    UnknownWord(String),
+    /// The phrase length can't hold a BIP-39 checksum
+    ChecksumLength(usize),
+    /// The checksum held by the phrase does not match its entropy
+    InvalidChecksum,
+    /// The entropy length can't be encoded as a BIP-39 phrase
+    EntropyLength(usize),
+    /// The dictionary does not have the 2048 words required by BIP-39
+    WordlistSize { name: String, size: usize },
+    /// The corpus does not hold enough words following the rules
+    CorpusTooSmall { found: usize, expected: usize },
+    /// A file could not be written
+    FileWrite(String),
+    /// An old Electrum seed must have 12 or 24 words
+    OldSeedLength(usize),
+    /// Three words of an old Electrum seed encode a value out of range
+    OldSeedOverflow(String),
+    /// A SLIP-39 share has a number of words that can't hold a secret
+    ShareLength(usize),
+    /// The padding bits of a SLIP-39 share are not zero
+    SharePadding,
+    /// The checksum of a SLIP-39 share is invalid
+    InvalidShareChecksum,
+    /// The group threshold of a SLIP-39 share is greater than its group count
+    GroupThreshold { threshold: usize, count: usize },
+    /// Shares combined together do not agree on a parameter
+    ShareMismatch(String),
+    /// The same member of a group was provided twice with different values
+    DuplicateShare { group: usize, member: usize },
+    /// Not enough shares were provided to recover a secret
+    NotEnoughShares { found: usize, expected: usize },
+    /// Not enough shares of a SLIP-39 group were provided
+    NotEnoughMembers {
+        group: usize,
+        found: usize,
+        expected: usize,
+    },
+    /// Not enough SLIP-39 groups were provided
+    NotEnoughGroups { found: usize, expected: usize },
+    /// The digest of the recovered secret does not match
+    InvalidDigest,
+    /// The envelope of an aezeed phrase has an unknown version
+    AezeedVersion(u8),
+    /// The checksum of an aezeed phrase does not match its content
+    AezeedChecksum,
+    /// An aezeed phrase can't be decrypted with the passphrase
+    AezeedDecryption,
+    /// A Monero seed must have 24 or 25 words
+    MoneroSeedLength(usize),
+    /// The checksum word of a Monero seed does not match
+    InvalidMoneroChecksum(String),
+    /// Three words of a Monero seed encode a value out of range
+    MoneroSeedOverflow(String),
+    /// A codex32 string has a length that can't hold a seed
+    Codex32Length(usize),
+    /// A codex32 string does not start with "ms1"
+    Codex32Prefix,
+    /// A codex32 string holds a character out of the bech32 alphabet
+    Codex32Character(char),
+    /// A codex32 string mixes upper and lower case characters
+    Codex32Case,
+    /// The checksum of a codex32 string is invalid
+    InvalidCodex32Checksum,
+    /// The threshold of a codex32 string is not 0 or 2 to 9
+    Codex32Threshold(char),
+    /// An unshared codex32 secret does not have the share index "s"
+    Codex32ShareIndex(char),
+    /// A codex32 string has more than 4 padding bits
+    Codex32Padding,
+    /// The same codex32 share index was provided twice with different values
+    Codex32DuplicateShare(char),
+    /// A SeedQR payload can't be decoded
+    InvalidSeedQr(String),
+    /// An image file could not be loaded
+    ImageLoad(String),
+    /// No QR code could be decoded from an image
+    QrCodeNotFound(String),
+    /// A Seed XOR needs at least two parts
+    SeedXorParts(usize),
+    /// The source of entropy is unknown
+    UnknownEntropySource(String),
+    /// A dice roll, coin flip or card can't be read
+    InvalidEntropySymbol { kind: String, symbol: String },
+    /// The same card was drawn twice from a deck
+    DuplicateCard(String),
+    /// Not enough entropy was provided for the length of the phrase
+    NotEnoughEntropy { provided: usize, needed: usize },
+    /// An extended private key can't be read
+    InvalidExtendedKey,
+    /// A BIP-32 derivation gives an invalid key
+    InvalidDerivation,
+    /// A BIP-85 length is out of the range of its application
+    Bip85Length {
+        application: String,
+        length: usize,
+        min: usize,
+        max: usize,
+    },
+    /// A language has no code in BIP-85 derivation paths
+    Bip85Language(String),
+    /// A word is not one of the 256 Bytewords
+    UnknownByteword(String),
+    /// The CRC-32 held by Bytewords does not match their content
+    InvalidBytewordsChecksum,
+    /// A UR string or its CBOR content is malformed
+    InvalidUr(String),
+    /// The UR type can't be converted to a phrase
+    UnsupportedUrType(String),
+    /// Not enough parts of a multipart UR were provided to decode it
+    IncompleteUr { found: usize, expected: usize },
+    /// An extended public key can't be decoded
+    InvalidExtendedPublicKey,
+    /// A derivation path is not written as m/index/index'...
+    InvalidDerivationPath(String),
+    /// A recovery target is not an address, an extended public key or a fingerprint
+    UnknownTarget(String),
+    /// An SSKR group is not written as a valid M-of-N
+    InvalidSskrGroup(String),
+    /// An SSKR share can't be decoded
+    InvalidSskrShare(String),
+    /// A position is not the position of a word of the phrase
+    InvalidPosition { position: usize, length: usize },
+    /// A checkpoint file can't be read
+    CheckpointLoad(String),
+    /// A checkpoint file can't be written
+    CheckpointSave(String),
+    /// A checkpoint file was saved by another search
+    CheckpointMismatch(String),
+    /// A file of candidate passphrases can't be read
+    PassphrasesLoad(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WordCount { expected, found } => write!(
+                f,
+                "Provided mnemonic seed is invalid. {} words expected, {} found",
+                expected, found
+            ),
+            Error::DictionaryLoad(path) => {
+                write!(f, "Error loading dictionary with path : {}", path)
+            }
+            Error::MissingWords(words) => write!(
+                f,
+                "One or many words were not found in dictionaries: {}",
+                words.join(",")
+            ),
+            Error::UnknownLanguage(name) => write!(f, "Unknown language : {}", name),
+            Error::UnknownWord(word) => write!(f, "Word not found in dictionary : {}", word),
+            Error::ChecksumLength(length) => write!(
+                f,
+                "A phrase of {} words can't hold a checksum (12, 15, 18, 21 or 24 words expected)",
+                length
+            ),
+            Error::InvalidChecksum => write!(f, "Checksum of the phrase is invalid"),
+            Error::EntropyLength(length) => write!(
+                f,
+                "Entropy of {} bytes can't be encoded (16, 20, 24, 28 or 32 bytes expected)",
+                length
+            ),
+            Error::WordlistSize { name, size } => {
+                write!(f, "Dictionary {} has {} words, 2048 expected", name, size)
+            }
+            Error::CorpusTooSmall { found, expected } => write!(
+                f,
+                "Corpus holds {} words following the rules, {} expected",
+                found, expected
+            ),
+            Error::FileWrite(path) => write!(f, "Error writing file with path : {}", path),
+            Error::OldSeedLength(length) => write!(
+                f,
+                "An old Electrum seed has 12 or 24 words, {} found",
+                length
+            ),
+            Error::ShareLength(length) => {
+                write!(f, "A share of {} words can't hold a valid secret", length)
+            }
+            Error::SharePadding => write!(f, "Padding of the share is invalid"),
+            Error::InvalidShareChecksum => write!(f, "Checksum of the share is invalid"),
+            Error::GroupThreshold { threshold, count } => write!(
+                f,
+                "Group threshold {} is greater than group count {}",
+                threshold, count
+            ),
+            Error::ShareMismatch(parameter) => write!(
+                f,
+                "Shares do not belong to the same secret, their {} differ",
+                parameter
+            ),
+            Error::DuplicateShare { group, member } => write!(
+                f,
+                "Member {} of group {} was provided twice with different values",
+                member, group
+            ),
+            Error::NotEnoughShares { found, expected } => write!(
+                f,
+                "{} shares provided, {} needed to recover the secret",
+                found, expected
+            ),
+            Error::NotEnoughMembers {
+                group,
+                found,
+                expected,
+            } => write!(
+                f,
+                "{} shares of group {} provided, {} needed",
+                found, group, expected
+            ),
+            Error::NotEnoughGroups { found, expected } => write!(
+                f,
+                "{} complete groups provided, {} needed to recover the secret",
+                found, expected
+            ),
+            Error::InvalidDigest => write!(
+                f,
+                "Digest of the recovered secret is invalid, shares may be corrupted"
+            ),
+            Error::OldSeedOverflow(words) => write!(
+                f,
+                "Words {} can't be decoded as part of an old Electrum seed",
+                words
+            ),
+            Error::AezeedVersion(version) => write!(f, "Unknown aezeed version {}", version),
+            Error::AezeedChecksum => write!(f, "Checksum of the aezeed phrase is invalid"),
+            Error::AezeedDecryption => write!(
+                f,
+                "Aezeed phrase can't be decrypted, the passphrase may be wrong"
+            ),
+            Error::MoneroSeedLength(length) => {
+                write!(f, "A Monero seed has 24 or 25 words, {} found", length)
+            }
+            Error::InvalidMoneroChecksum(expected) => write!(
+                f,
+                "Checksum word of the Monero seed is invalid, {} expected",
+                expected
+            ),
+            Error::MoneroSeedOverflow(words) => write!(
+                f,
+                "Words {} can't be decoded as part of a Monero seed",
+                words
+            ),
+            Error::Codex32Length(length) => {
+                write!(
+                    f,
+                    "A codex32 string of {} characters can't hold a seed",
+                    length
+                )
+            }
+            Error::Codex32Prefix => write!(f, "A codex32 string must start with ms1"),
+            Error::Codex32Character(character) => write!(
+                f,
+                "Character {} is not part of the bech32 alphabet",
+                character
+            ),
+            Error::Codex32Case => write!(f, "Codex32 string mixes upper and lower case"),
+            Error::InvalidCodex32Checksum => write!(f, "Checksum of the codex32 string is invalid"),
+            Error::Codex32Threshold(threshold) => {
+                write!(
+                    f,
+                    "Threshold {} is invalid, 0 or 2 to 9 expected",
+                    threshold
+                )
+            }
+            Error::Codex32ShareIndex(index) => write!(
+                f,
+                "Share {} can't recover an unshared secret, share s expected",
+                index
+            ),
+            Error::Codex32Padding => write!(f, "Padding of the codex32 string is invalid"),
+            Error::Codex32DuplicateShare(index) => write!(
+                f,
+                "Share {} was provided twice with different values",
+                index
+            ),
+            Error::InvalidSeedQr(reason) => write!(f, "Invalid SeedQR : {}", reason),
+            Error::ImageLoad(path) => write!(f, "Error loading image with path : {}", path),
+            Error::QrCodeNotFound(path) => write!(f, "No QR code found in image {}", path),
+            Error::SeedXorParts(parts) => {
+                write!(f, "A Seed XOR needs at least 2 parts, {} provided", parts)
+            }
+            Error::UnknownEntropySource(name) => write!(
+                f,
+                "Unknown source of entropy {}, dice, coins or cards expected",
+                name
+            ),
+            Error::InvalidEntropySymbol { kind, symbol } => {
+                write!(f, "{} is not a valid {}", symbol, kind)
+            }
+            Error::DuplicateCard(card) => write!(f, "Card {} was drawn twice", card),
+            Error::NotEnoughEntropy { provided, needed } => write!(
+                f,
+                "{} bits of entropy provided, {} needed",
+                provided, needed
+            ),
+            Error::InvalidExtendedKey => write!(f, "Extended private key is invalid"),
+            Error::InvalidDerivation => {
+                write!(f, "Derivation gives an invalid key, use another index")
+            }
+            Error::Bip85Length {
+                application,
+                length,
+                min,
+                max,
+            } => write!(
+                f,
+                "Length {} is invalid for {}, {} to {} expected",
+                length, application, min, max
+            ),
+            Error::Bip85Language(language) => {
+                write!(f, "Language {} has no code in BIP-85", language)
+            }
+            Error::UnknownByteword(word) => write!(f, "{} is not a byteword", word),
+            Error::InvalidBytewordsChecksum => write!(f, "Checksum of the bytewords is invalid"),
+            Error::InvalidUr(reason) => write!(f, "Invalid UR : {}", reason),
+            Error::UnsupportedUrType(ur_type) => write!(
+                f,
+                "UR type {} is not supported, crypto-seed or crypto-bip39 expected",
+                ur_type
+            ),
+            Error::IncompleteUr { found, expected } => write!(
+                f,
+                "{} fragments of {} decoded, more parts are needed",
+                found, expected
+            ),
+            Error::InvalidExtendedPublicKey => write!(f, "Extended public key is invalid"),
+            Error::InvalidDerivationPath(path) => {
+                write!(
+                    f,
+                    "Derivation path {} is invalid, m/84'/0'/0' expected",
+                    path
+                )
+            }
+            Error::UnknownTarget(target) => write!(
+                f,
+                "{} is not an address, an extended public key or a fingerprint",
+                target
+            ),
+            Error::InvalidSskrGroup(group) => write!(
+                f,
+                "Group {} is invalid, M-of-N expected with M from 1 to N and N up to 16",
+                group
+            ),
+            Error::InvalidSskrShare(reason) => write!(f, "Invalid SSKR share : {}", reason),
+            Error::InvalidPosition { position, length } => write!(
+                f,
+                "Position {} is invalid, positions go from 1 to {}",
+                position, length
+            ),
+            Error::CheckpointLoad(path) => {
+                write!(f, "Error loading checkpoint with path : {}", path)
+            }
+            Error::CheckpointSave(path) => {
+                write!(f, "Error saving checkpoint with path : {}", path)
+            }
+            Error::PassphrasesLoad(path) => {
+                write!(f, "Error loading passphrases with path : {}", path)
+            }
+            Error::CheckpointMismatch(path) => write!(
+                f,
+                "Checkpoint {} was saved by another search, its parameters differ",
+                path
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test]
+fn test_error_display() {
+    let error = Error::WordCount {
+        expected: 24,
+        found: 2,
+    };
+    assert_eq!(
+        "Provided mnemonic seed is invalid. 24 words expected, 2 found",
+        error.to_string()
+    );
+
+    let error = Error::MissingWords(vec!["toto".to_string(), "tata".to_string()]);
+    assert_eq!(
+        "One or many words were not found in dictionaries: toto,tata",
+        error.to_string()
+    );
+}
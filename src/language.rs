@@ -0,0 +1,114 @@
+use crate::dictionaries::langs::{
+    CHINESE_SIMPLIFIED, CHINESE_TRADITIONAL, CZECH, ENGLISH, FRENCH, ITALIAN, JAPANESE, KOREAN,
+    PORTUGUESE, SPANISH,
+};
+use crate::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/**
+ * Languages of the built-in BIP-39 dictionaries
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    French,
+    Czech,
+    Italian,
+    Spanish,
+    Portuguese,
+    Japanese,
+    Korean,
+    ChineseTraditional,
+    ChineseSimplified,
+}
+
+impl Language {
+    /**
+     * All the built-in languages, in the order dictionaries are searched
+     */
+    pub const ALL: [Language; 10] = [
+        Language::English,
+        Language::French,
+        Language::Czech,
+        Language::Italian,
+        Language::Spanish,
+        Language::Portuguese,
+        Language::Japanese,
+        Language::Korean,
+        Language::ChineseTraditional,
+        Language::ChineseSimplified,
+    ];
+
+    /**
+     * Returns the built-in dictionary of the language
+     */
+    pub fn words(self) -> &'static [&'static str; 2048] {
+        match self {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+            Language::Czech => &CZECH,
+            Language::Italian => &ITALIAN,
+            Language::Spanish => &SPANISH,
+            Language::Portuguese => &PORTUGUESE,
+            Language::Japanese => &JAPANESE,
+            Language::Korean => &KOREAN,
+            Language::ChineseTraditional => &CHINESE_TRADITIONAL,
+            Language::ChineseSimplified => &CHINESE_SIMPLIFIED,
+        }
+    }
+
+    /**
+     * Returns the name of the language, as used on the command line
+     */
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::French => "french",
+            Language::Czech => "czech",
+            Language::Italian => "italian",
+            Language::Spanish => "spanish",
+            Language::Portuguese => "portuguese",
+            Language::Japanese => "japanese",
+            Language::Korean => "korean",
+            Language::ChineseTraditional => "chinese_traditional",
+            Language::ChineseSimplified => "chinese_simplified",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Language, Error> {
+        let lowercase = name.trim().to_lowercase().replace('-', "_");
+        Language::ALL
+            .iter()
+            .find(|language| language.name() == lowercase)
+            .copied()
+            .ok_or_else(|| Error::UnknownLanguage(name.to_string()))
+    }
+}
+
+#[test]
+fn test_language_words() {
+    assert_eq!("abandon", Language::English.words()[0]);
+    assert_eq!("zoo", Language::English.words()[2047]);
+    assert_eq!("abaisser", Language::French.words()[0]);
+}
+
+#[test]
+fn test_language_from_str() {
+    assert_eq!(Language::French, "french".parse().unwrap());
+    assert_eq!(
+        Language::ChineseSimplified,
+        "Chinese-Simplified".parse().unwrap()
+    );
+    assert!("klingon".parse::<Language>().is_err());
+}
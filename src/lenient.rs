@@ -0,0 +1,181 @@
+use crate::wordlist::Wordlist;
+use std::fmt;
+
+/**
+ * Quotation marks found around words copied from documents
+ */
+const QUOTES: [char; 17] = [
+    '"', '\'', '`', '\u{AB}', '\u{BB}', '\u{2018}', '\u{2019}', '\u{201A}', '\u{201C}', '\u{201D}',
+    '\u{201E}', '\u{2039}', '\u{203A}', '\u{300C}', '\u{300D}', '\u{300E}', '\u{300F}',
+];
+
+/**
+ * Punctuation marks besides ASCII ones found around words
+ */
+const PUNCTUATION: [char; 8] = [
+    '\u{A1}', '\u{BF}', '\u{2026}', '\u{3002}', '\u{FF01}', '\u{FF08}', '\u{FF09}', '\u{FF1F}',
+];
+
+fn is_quote(character: char) -> bool {
+    QUOTES.contains(&character)
+}
+
+fn is_punctuation(character: char) -> bool {
+    character.is_ascii_punctuation() || is_quote(character) || PUNCTUATION.contains(&character)
+}
+
+/**
+ * A change made to a word so it matches a dictionary entry
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Uppercase letters were lowercased
+    Lowercased,
+    /// Quotation marks around the word were removed
+    Unquoted,
+    /// Punctuation marks around the word were removed
+    Punctuation(String),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Lowercased => write!(f, "lowercased"),
+            Change::Unquoted => write!(f, "removed quotes"),
+            Change::Punctuation(marks) => write!(f, "removed punctuation {:?}", marks),
+        }
+    }
+}
+
+/**
+ * A word of the phrase that was changed before being searched.
+ * Position is the index of the word in the phrase, starting at 1.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    pub position: usize,
+    pub original: String,
+    pub word: String,
+    pub changes: Vec<Change>,
+}
+
+impl fmt::Display for Correction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let changes: Vec<String> = self.changes.iter().map(|c| c.to_string()).collect();
+        write!(
+            f,
+            "word {} {:?} read as {:?} ({})",
+            self.position,
+            self.original,
+            self.word,
+            changes.join(", ")
+        )
+    }
+}
+
+/**
+ * Removes punctuation and quotes around a word and lowercases it.
+ * Returns the normalized word and the changes made to it.
+ */
+pub fn normalize(word: &str) -> (String, Vec<Change>) {
+    let mut changes = Vec::new();
+
+    let trimmed = word.trim_matches(is_punctuation);
+    let removed: String = word
+        .chars()
+        .take_while(|&c| is_punctuation(c))
+        .chain(
+            word.chars()
+                .rev()
+                .take_while(|&c| is_punctuation(c))
+                .collect::<Vec<char>>()
+                .into_iter()
+                .rev(),
+        )
+        .collect();
+    // A word made only of punctuation is left as it is
+    let trimmed = if trimmed.is_empty() { word } else { trimmed };
+    if trimmed != word {
+        if removed.chars().any(is_quote) {
+            changes.push(Change::Unquoted);
+        }
+        let marks: String = removed.chars().filter(|&c| !is_quote(c)).collect();
+        if !marks.is_empty() {
+            changes.push(Change::Punctuation(marks));
+        }
+    }
+
+    let lowercase = trimmed.to_lowercase();
+    if lowercase != trimmed {
+        changes.push(Change::Lowercased);
+    }
+
+    (lowercase, changes)
+}
+
+/**
+ * Normalizes the words of a phrase that are not found as they are
+ * in any of the dictionaries.
+ * Returns the words to search and the corrections made.
+ */
+pub fn normalize_words(
+    words: &[String],
+    wordlists: &[Box<dyn Wordlist>],
+) -> (Vec<String>, Vec<Correction>) {
+    let mut corrections = Vec::new();
+    let normalized = words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            if wordlists.iter().any(|wordlist| wordlist.contains(word)) {
+                return word.clone();
+            }
+            let (normalized, changes) = normalize(word);
+            if !changes.is_empty() {
+                corrections.push(Correction {
+                    position: index + 1,
+                    original: word.clone(),
+                    word: normalized.clone(),
+                    changes,
+                });
+            }
+            normalized
+        })
+        .collect();
+    (normalized, corrections)
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(("erupt".to_string(), vec![]), normalize("erupt"));
+    assert_eq!(
+        ("erupt".to_string(), vec![Change::Lowercased]),
+        normalize("Erupt")
+    );
+    assert_eq!(
+        (
+            "quit".to_string(),
+            vec![Change::Unquoted, Change::Punctuation(".".to_string())]
+        ),
+        normalize("\u{201C}quit.\u{201D}")
+    );
+    assert_eq!(("...".to_string(), vec![]), normalize("..."));
+}
+
+#[test]
+fn test_normalize_words() {
+    use crate::wordlist::builtin_wordlists;
+
+    let words = vec![
+        "Erupt".to_string(),
+        "quit!".to_string(),
+        "sphere".to_string(),
+    ];
+    let (normalized, corrections) = normalize_words(&words, &builtin_wordlists());
+    assert_eq!(vec!["erupt", "quit", "sphere"], normalized);
+    assert_eq!(2, corrections.len());
+    assert_eq!(
+        "word 2 \"quit!\" read as \"quit\" (removed punctuation \"!\")",
+        corrections[1].to_string()
+    );
+}
@@ -0,0 +1,45 @@
+//! A simple BIP-39 mnemonic words checker.
+//!
+//! Checks that the words of a mnemonic phrase are part of the BIP-39
+//! dictionaries, either the built-in ones or dictionary files, and
+//! verifies the checksum held by the phrase.
+
+mod aez;
+pub mod aezeed;
+mod base58;
+pub mod bip32;
+pub mod bip85;
+pub mod builder;
+pub mod bytewords;
+pub mod checkpoint;
+pub mod codex32;
+pub mod collisions;
+pub mod confusables;
+pub mod dictionaries;
+pub mod electrum;
+pub mod encoding;
+pub mod entropy;
+mod error;
+mod language;
+pub mod lenient;
+pub mod lint;
+mod mnemonic;
+pub mod monero;
+pub mod oracle;
+pub mod recovery;
+pub mod search;
+pub mod seedqr;
+pub mod seedxor;
+pub mod shamir;
+pub mod slip39;
+pub mod sskr;
+pub mod suggest;
+pub mod tokenizer;
+pub mod typos;
+pub mod ur;
+mod wordlist;
+
+pub use error::Error;
+pub use language::Language;
+pub use mnemonic::Mnemonic;
+pub use wordlist::{builtin_schemes, builtin_wordlists, ExternalWordlist, Scheme, Wordlist};
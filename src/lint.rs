@@ -0,0 +1,262 @@
+use crate::error::Error;
+use crate::mnemonic::Mnemonic;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * Number of leading characters that should identify a word
+ */
+pub const PREFIX_LENGTH: usize = 4;
+
+/**
+ * How serious an issue found in a dictionary is.
+ * A dictionary with errors can't be used for BIP-39,
+ * warnings may only prevent some words from being matched.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/**
+ * An issue found in a dictionary file.
+ * Line numbers start at 1.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintIssue {
+    /// The dictionary does not have 2048 entries
+    WordCount(usize),
+    /// The same entry is found on several lines
+    Duplicate { word: String, lines: Vec<usize> },
+    /// The line is blank or has whitespace around or inside the entry
+    Whitespace { line: usize, word: String },
+    /// The entry is not NFKD normalized
+    NotNormalized { line: usize, word: String },
+    /// The entry has uppercase letters
+    NotLowercase { line: usize, word: String },
+    /// Several entries start with the same four characters
+    PrefixCollision { prefix: String, lines: Vec<usize> },
+}
+
+impl LintIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintIssue::WordCount(_)
+            | LintIssue::Duplicate { .. }
+            | LintIssue::NotNormalized { .. }
+            | LintIssue::NotLowercase { .. } => Severity::Error,
+            // Whitespace around an entry is trimmed when loading it
+            LintIssue::Whitespace { word, .. } if word.trim().contains(char::is_whitespace) => {
+                Severity::Error
+            }
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintIssue::WordCount(count) => write!(
+                f,
+                "dictionary has {} words, {} expected",
+                count,
+                Mnemonic::WORDLIST_SIZE
+            ),
+            LintIssue::Duplicate { word, lines } => {
+                write!(f, "lines {} : duplicate word {}", join(lines), word)
+            }
+            LintIssue::Whitespace { line, word } => {
+                write!(f, "line {} : unexpected whitespace in {:?}", line, word)
+            }
+            LintIssue::NotNormalized { line, word } => {
+                write!(f, "line {} : {} is not NFKD normalized", line, word)
+            }
+            LintIssue::NotLowercase { line, word } => {
+                write!(f, "line {} : {} is not lowercase", line, word)
+            }
+            LintIssue::PrefixCollision { prefix, lines } => write!(
+                f,
+                "lines {} : words share the prefix {}",
+                join(lines),
+                prefix
+            ),
+        }
+    }
+}
+
+fn join(lines: &[usize]) -> String {
+    lines
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/**
+ * Returns the leading characters of a word, compared in their
+ * composed form so accents count as part of the letter they mark
+ */
+pub fn prefix(word: &str) -> String {
+    word.nfc().take(PREFIX_LENGTH).collect()
+}
+
+/**
+ * Checks the content of a dictionary file.
+ * Entries are compared after trimming, as they are when the
+ * dictionary is loaded.
+ */
+pub fn lint(content: &str) -> Vec<LintIssue> {
+    let mut issues: Vec<LintIssue> = Vec::new();
+    let mut count = 0;
+    let mut words: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut prefixes: HashMap<String, Vec<usize>> = HashMap::new();
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let word = raw.trim();
+
+        if word.is_empty() || word != raw || word.chars().any(char::is_whitespace) {
+            issues.push(LintIssue::Whitespace {
+                line,
+                word: raw.to_string(),
+            });
+        }
+        if word.is_empty() {
+            continue;
+        }
+
+        count += 1;
+        if word.nfkd().collect::<String>() != word {
+            issues.push(LintIssue::NotNormalized {
+                line,
+                word: word.to_string(),
+            });
+        }
+        if word.to_lowercase() != word {
+            issues.push(LintIssue::NotLowercase {
+                line,
+                word: word.to_string(),
+            });
+        }
+
+        words.entry(word.to_string()).or_default().push(line);
+        prefixes.entry(prefix(word)).or_default().push(line);
+    }
+
+    if count != Mnemonic::WORDLIST_SIZE {
+        issues.insert(0, LintIssue::WordCount(count));
+    }
+
+    let mut duplicates: Vec<LintIssue> = words
+        .into_iter()
+        .filter(|(_, lines)| lines.len() > 1)
+        .map(|(word, lines)| LintIssue::Duplicate { word, lines })
+        .collect();
+    duplicates.sort_by_key(first_line);
+
+    let mut collisions: Vec<LintIssue> = prefixes
+        .into_iter()
+        .filter(|(_, lines)| lines.len() > 1)
+        .map(|(prefix, lines)| LintIssue::PrefixCollision { prefix, lines })
+        .collect();
+    collisions.sort_by_key(first_line);
+
+    issues.extend(duplicates);
+    issues.extend(collisions);
+    issues
+}
+
+fn first_line(issue: &LintIssue) -> usize {
+    match issue {
+        LintIssue::Duplicate { lines, .. } | LintIssue::PrefixCollision { lines, .. } => lines[0],
+        LintIssue::Whitespace { line, .. }
+        | LintIssue::NotNormalized { line, .. }
+        | LintIssue::NotLowercase { line, .. } => *line,
+        LintIssue::WordCount(_) => 0,
+    }
+}
+
+/**
+ * Checks a dictionary file.
+ * Will return an Err if path is invalid.
+ */
+pub fn lint_file(path: &str) -> Result<Vec<LintIssue>, Error> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(lint(&content)),
+        Err(_) => Err(Error::DictionaryLoad(path.to_string())),
+    }
+}
+
+#[test]
+fn test_lint_builtin_dictionaries() {
+    use crate::language::Language;
+
+    for language in Language::ALL.iter() {
+        let content = language.words().join("\n");
+        assert_eq!(Vec::<LintIssue>::new(), lint(&content), "{}", language);
+    }
+}
+
+#[test]
+fn test_lint() {
+    let content = "abandon\nAbility\n able\nabandon\nabsent\n\ncafe\u{301}\ncaf\u{e9}";
+    let issues = lint(content);
+
+    assert_eq!(LintIssue::WordCount(7), issues[0]);
+    assert!(issues.contains(&LintIssue::NotLowercase {
+        line: 2,
+        word: "Ability".to_string()
+    }));
+    assert!(issues.contains(&LintIssue::Whitespace {
+        line: 3,
+        word: " able".to_string()
+    }));
+    assert!(issues.contains(&LintIssue::Whitespace {
+        line: 6,
+        word: "".to_string()
+    }));
+    assert!(issues.contains(&LintIssue::NotNormalized {
+        line: 8,
+        word: "caf\u{e9}".to_string()
+    }));
+    assert!(issues.contains(&LintIssue::Duplicate {
+        word: "abandon".to_string(),
+        lines: vec![1, 4]
+    }));
+    assert!(issues.contains(&LintIssue::PrefixCollision {
+        prefix: "caf\u{e9}".to_string(),
+        lines: vec![7, 8]
+    }));
+    assert_eq!(Severity::Error, issues[0].severity());
+    for issue in &issues {
+        let expected = match issue {
+            LintIssue::Whitespace { line: 3, .. }
+            | LintIssue::Whitespace { line: 6, .. }
+            | LintIssue::PrefixCollision { .. } => Severity::Warning,
+            _ => Severity::Error,
+        };
+        assert_eq!(expected, issue.severity(), "{}", issue);
+    }
+    let inside = LintIssue::Whitespace {
+        line: 1,
+        word: "hello world".to_string(),
+    };
+    assert_eq!(Severity::Error, inside.severity());
+}
+
+#[test]
+fn test_lint_file() {
+    let issues = lint_file("resources/test/test_dictionary.txt").unwrap();
+    assert_eq!(
+        vec![LintIssue::Whitespace {
+            line: 1,
+            word: " abaisser".to_string()
+        }],
+        issues
+    );
+    assert!(lint_file("resources/test/invalid_dictionary.txt").is_err());
+}
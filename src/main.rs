@@ -1,201 +1,335 @@
-mod dictionaries;
-
-use assert_cmd::prelude::*; // Add methods on commands
-use predicates::prelude::*; // Used for writing assertions
-use std::process::Command;
+mod commands;
 
+use b39wc::aezeed::Envelope;
+use b39wc::collisions::{ambiguity_probability, shared_words};
+use b39wc::confusables::inspect;
+use b39wc::electrum::{self, ElectrumOldWordlist};
+use b39wc::encoding::to_hex;
+use b39wc::lenient::normalize_words;
+use b39wc::monero::{self, MoneroWordlist};
+use b39wc::slip39::{Share, Slip39Wordlist};
+use b39wc::suggest::suggest_all;
+use b39wc::tokenizer::{tokenize, Layout, Tokens};
+use b39wc::{
+    builtin_schemes, builtin_wordlists, Error, ExternalWordlist, Mnemonic, Scheme, Wordlist,
+};
 use clap::{App, Arg};
 use colour::green_ln;
 use colour::red_ln;
-use dictionaries::langs::{
-    CHINESE_SIMPLIFIED, CHINESE_TRADITIONAL, CZECH, ENGLISH, FRENCH, ITALIAN, JAPANESE, KOREAN,
-    PORTUGUESE, SPANISH,
-};
-use regex::Regex;
-use std::fs;
+use colour::yellow_ln;
 use std::process::exit;
-use std::str::SplitWhitespace;
-
-fn find_in_dictionary(dictionary: Vec<&str>, word: &str) -> bool {
-    let mut result: bool = false;
-    if dictionary.iter().any(|&w| w == word) {
-        result = true;
-    }
-
-    return result;
-}
-
-/**
- * Splits the seed string into an iterable of seperated words
- */
-fn get_words(content: &str) -> SplitWhitespace<'_> {
-    let splitted_content = content.split_whitespace();
-    let s_re = Regex::new(r"\s").unwrap();
-
-    println!("words to be searched : {}", s_re.replace_all(content, ", "));
-
-    return splitted_content;
-}
 
-#[test]
-fn test_get_words() {
-    let test_seed: &str = "abandon toto tata";
-    let results: Vec<&str> = get_words(test_seed).collect();
-    assert_eq!("abandon", results[0]);
-    assert_eq!("toto", results[1]);
-    assert_ne!("abandon", results[1]);
-    assert_eq!("tata", results[2]);
-}
+#[cfg(test)]
+use assert_cmd::prelude::*; // Add methods on commands
+#[cfg(test)]
+use predicates::prelude::*; // Used for writing assertions
+#[cfg(test)]
+use std::process::Command;
 
 /**
  * Splits a string based on coma separator.
  * Used to split provided paths by user.
  */
 fn process_paths(paths: &str) -> Vec<&str> {
-    return paths.split(',').collect();
+    paths.split(',').map(|path| path.trim()).collect()
 }
 
 #[test]
 fn test_process_paths() {
     let single_path: &str = "./my/path.txt";
     let multiple_paths: &str = "./my/path1.txt,./my/path2.txt";
-    assert_eq!(1, process_paths(single_path).iter().count());
-    assert_eq!(2, process_paths(multiple_paths).iter().count());
+    assert_eq!(1, process_paths(single_path).len());
+    assert_eq!(2, process_paths(multiple_paths).len());
+}
+
+/**
+ * Loads the dictionaries provided by user
+ */
+fn load_wordlists(paths: Vec<&str>) -> Result<Vec<Box<dyn Wordlist>>, Error> {
+    paths
+        .into_iter()
+        .map(|path| ExternalWordlist::load(path).map(|w| Box::new(w) as Box<dyn Wordlist>))
+        .collect()
 }
 
 /**
- * Checks the number of words in a string instance.
- * Fails if string instance has more or less words than 24
- * which is the official mnemonic phrase length
+ * Reports the dictionaries holding the whole phrase
+ * and whether the checksum of the phrase is valid for them
  */
-fn check_words_number(content: &str) -> Result<bool, usize> {
-    let splitted_content = content.split_whitespace();
+fn report_matching_wordlists(mnemonic: &Mnemonic, wordlists: &[Box<dyn Wordlist>]) {
+    let matching = mnemonic.matching_wordlists(wordlists);
+    if matching.is_empty() {
+        yellow_ln!("No single dictionary holds all the provided words");
+        return;
+    }
+
+    let names: Vec<String> = matching.iter().map(|wordlist| wordlist.name()).collect();
+    println!("Matching dictionaries : {}", names.join(", "));
+    let canonical = matching
+        .iter()
+        .find(|wordlist| mnemonic.verify_checksum(**wordlist).is_ok())
+        .unwrap_or(&matching[0]);
+    println!("Canonical phrase : {}", mnemonic.to_phrase(*canonical));
+    if matching.len() > 1 {
+        explain_ambiguity(mnemonic, &matching);
+    }
 
-    if splitted_content.clone().count() != 24 {
-        return Err(splitted_content.clone().count());
+    for wordlist in matching {
+        match mnemonic.verify_checksum(wordlist) {
+            Ok(()) => {
+                green_ln!("Checksum is valid with dictionary {}", wordlist.name());
+            }
+            Err(error) => {
+                yellow_ln!("{} with dictionary {}", error, wordlist.name());
+            }
+        }
     }
-    return Ok(true);
 }
 
-#[test]
-fn test_check_words_number() {
-    let test_seed: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant verb";
-    let test_seed_failure: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid";
-    let result = check_words_number(test_seed);
-    assert!(result.is_ok());
-    assert_eq!(true, result.unwrap());
-    let result = check_words_number(test_seed_failure);
-    assert!(result.is_err());
+/**
+ * Returns the seed schemes other than BIP-39 whose dictionary
+ * holds the whole phrase
+ */
+fn matching_schemes(mnemonic: &Mnemonic) -> Vec<Scheme> {
+    builtin_schemes()
+        .into_iter()
+        .filter(|scheme| scheme.contains_all(mnemonic.words()))
+        .collect()
 }
 
 /**
- * Performs seed words check with built-in provided dictionaries
+ * Decodes a phrase with each seed scheme whose dictionary holds it
  */
-fn check_from_builtin_dictionaries(words: SplitWhitespace) -> (bool, Vec<String>) {
-    let mut missing: Vec<String> = Vec::new();
-    let mut all_found = true;
-
-    '_outer: for word in words.into_iter() {
-        let mut is_found = false;
-        '_inner: for dictionary in [
-            ENGLISH,
-            FRENCH,
-            CZECH,
-            ITALIAN,
-            SPANISH,
-            PORTUGUESE,
-            JAPANESE,
-            KOREAN,
-            CHINESE_TRADITIONAL,
-            CHINESE_SIMPLIFIED,
-        ]
-        .to_vec()
-        {
-            match self::find_in_dictionary(dictionary.to_vec(), word) {
-                true => {
-                    is_found = true;
-                }
-                false => {}
-            }
+fn report_schemes(mnemonic: &Mnemonic, schemes: &[Scheme]) {
+    if schemes.is_empty() {
+        return;
+    }
+    let names: Vec<String> = schemes
+        .iter()
+        .map(|scheme| scheme.wordlist().name())
+        .collect();
+    println!("Matching dictionaries : {}", names.join(", "));
+    for scheme in schemes {
+        match scheme {
+            Scheme::ElectrumOld => report_old_electrum(mnemonic),
+            Scheme::Slip39 => report_slip39_share(mnemonic),
+            Scheme::Monero(wordlist) => report_monero(mnemonic, *wordlist),
         }
+    }
+}
 
-        if !is_found {
-            all_found = false;
-            missing.push(word.clone().to_string());
+/**
+ * Explains why a phrase could belong to more than one dictionary
+ */
+fn explain_ambiguity(mnemonic: &Mnemonic, matching: &[&dyn Wordlist]) {
+    yellow_ln!(
+        "Provided words could belong to {} dictionaries",
+        matching.len()
+    );
+    for (i, first) in matching.iter().enumerate() {
+        for second in matching.iter().skip(i + 1) {
+            println!(
+                "{} and {} share {} words",
+                first.name(),
+                second.name(),
+                shared_words(*first, *second).len()
+            );
         }
     }
-
-    return (all_found, missing);
+    println!(
+        "A random {} words phrase of {} also belongs to another of them with probability {:.2e}",
+        mnemonic.len(),
+        matching[0].name(),
+        ambiguity_probability(matching[0], &matching[1..], mnemonic.len())
+    );
 }
 
 /**
- * loads the content of a file.
- * Will return an Err if path is invalid.
- * Used to load provided dictionaries content
+ * Builds the phrase from its words in the order they were meant to be read.
+ * A grid is read row by row, unless only reading it column by column
+ * gives a valid checksum.
  */
-fn load_dictionary(path: &str) -> Result<String, &str> {
-    match fs::read_to_string(path) {
-        Ok(data) => Ok(data),
-        Err(_) => Err(path),
+fn read_phrase(tokens: Tokens, wordlists: &[Box<dyn Wordlist>]) -> Mnemonic {
+    match tokens.layout {
+        Layout::Numbered => println!("Words were sorted by their numbers"),
+        Layout::Grid { rows, columns } => {
+            let row_major = Mnemonic::from_words(tokens.words.clone());
+            let column_major = Mnemonic::from_words(tokens.column_major().unwrap_or_default());
+            if !row_major.has_valid_checksum(wordlists)
+                && column_major.has_valid_checksum(wordlists)
+            {
+                println!(
+                    "Words were read column by column from a grid of {} rows and {} columns",
+                    rows, columns
+                );
+                return column_major;
+            }
+        }
+        Layout::Plain => {}
     }
+    Mnemonic::from_words(tokens.words)
 }
 
-#[test]
-fn test_load_dictionary() {
-    let invalid_path: &str = "resources/test/invalid_dictionary.txt";
-    assert!(load_dictionary(invalid_path).is_err());
+/**
+ * Decodes a phrase of the old Electrum dictionary to its hex seed
+ */
+fn report_old_electrum(mnemonic: &Mnemonic) {
+    match electrum::decode_old_seed(mnemonic.words()) {
+        Ok(seed) => {
+            green_ln!(
+                "Phrase is a valid old Electrum seed, hex seed : {}",
+                to_hex(&seed)
+            );
+        }
+        Err(error) => {
+            yellow_ln!("{} with dictionary {}", error, ElectrumOldWordlist.name());
+        }
+    }
+}
 
-    let valid_path: &str = "resources/test/test_dictionary.txt";
-    let result: Result<String, &str> = load_dictionary(valid_path);
-    assert!(result.is_ok());
-    assert!(result.unwrap().as_str().contains("loterie"));
-    // assert_eq!("hello world",result.unwrap().as_str());
+/**
+ * Checks a phrase of the SLIP-39 dictionary as a single share
+ */
+fn report_slip39_share(mnemonic: &Mnemonic) {
+    match Share::from_words(mnemonic.words()) {
+        Ok(share) => {
+            green_ln!("Phrase is a valid SLIP-39 share : {}", share);
+            println!("Use the slip39 command with enough shares to recover the master secret");
+        }
+        Err(error) => {
+            yellow_ln!("{} with dictionary {}", error, Slip39Wordlist.name());
+        }
+    }
 }
 
 /**
- * Performs a seed words check with external dictionaries provided by user
+ * Checks a phrase of a Monero dictionary as a Monero seed
  */
-fn check_from_external_dictionaries(
-    paths: Vec<&str>,
-    words: SplitWhitespace,
-) -> (bool, Vec<String>) {
-    let mut missing: Vec<String> = Vec::new();
-    let mut dictionaries: Vec<String> = Vec::new();
-    for path in paths {
-        match self::load_dictionary(path) {
-            Ok(dictionary) => {
-                dictionaries.push(dictionary);
-            }
-            Err(failed_path) => {
-                red_ln!("Error loading dictionary with path : {}", failed_path);
-                exit(1);
-            }
+fn report_monero(mnemonic: &Mnemonic, wordlist: MoneroWordlist) {
+    match monero::decode_seed(mnemonic.words(), wordlist) {
+        Ok(_) if mnemonic.len() == monero::SEED_WORDS => {
+            yellow_ln!(
+                "Phrase is a Monero seed without its checksum word, which should be {}",
+                monero::checksum_word(mnemonic.words(), wordlist)
+            );
         }
+        Ok(_) => {
+            green_ln!(
+                "Phrase is a valid Monero seed with dictionary {}",
+                wordlist.name()
+            );
+        }
+        Err(error) => {
+            yellow_ln!("{} with dictionary {}", error, wordlist.name());
+        }
+    }
+}
+
+/**
+ * Warns when the phrase is an Electrum seed, which restores
+ * to a different wallet than a BIP-39 phrase
+ */
+fn report_electrum(mnemonic: &Mnemonic, wordlists: &[Box<dyn Wordlist>]) {
+    let seed_type = match electrum::seed_type(&mnemonic.to_string()) {
+        Some(seed_type) => seed_type,
+        None => return,
+    };
+
+    if mnemonic.has_valid_checksum(wordlists) {
+        red_ln!(
+            "WARNING : phrase is both a valid BIP-39 phrase and an Electrum {} seed, \
+             restore it with the wallet that created it",
+            seed_type
+        );
+    } else {
+        red_ln!(
+            "WARNING : phrase is an Electrum {} seed, not a BIP-39 phrase, \
+             restoring it in a BIP-39 wallet will give a different wallet",
+            seed_type
+        );
     }
+}
 
-    let mut all_found = true;
+/**
+ * Warns when the phrase is an LND aezeed, and decrypts it
+ * to show the version and birthday of the wallet
+ */
+fn report_aezeed(mnemonic: &Mnemonic, wordlists: &[Box<dyn Wordlist>], passphrase: &str) {
+    let envelope = match Envelope::from_words(mnemonic.words()) {
+        Ok(envelope) => envelope,
+        Err(_) => return,
+    };
 
-    '_outer: for word in words.into_iter() {
-        let mut is_found = false;
-        '_inner: for dictionary in dictionaries.iter() {
-            let regex_string = format!(r"{}", &word);
-            let regex = Regex::new(regex_string.as_str()).unwrap();
+    if mnemonic.has_valid_checksum(wordlists) {
+        red_ln!(
+            "WARNING : phrase is both a valid BIP-39 phrase and an LND aezeed, \
+             restore it with the wallet that created it"
+        );
+    } else {
+        red_ln!(
+            "WARNING : phrase is an LND aezeed, not a BIP-39 phrase, \
+             it can only be restored with LND"
+        );
+    }
 
-            match regex.is_match(&dictionary.as_str()) {
-                true => {
-                    is_found = true;
-                }
-                false => {}
+    match envelope.decrypt(passphrase) {
+        Ok(seed) => {
+            green_ln!(
+                "Aezeed decrypted : version {}, wallet birthday {} (day {})",
+                seed.version,
+                seed.birthday_date(),
+                seed.birthday
+            );
+        }
+        Err(error) => {
+            yellow_ln!("{}", error);
+            if passphrase.is_empty() {
+                println!("Use --passphrase if the aezeed was encrypted with one");
             }
         }
+    }
+}
 
-        if !is_found {
-            missing.push(word.clone().to_string());
-            all_found = false;
+/**
+ * Prints the characters of words that were not found which look like
+ * latin letters, and the built-in word they were made to look like.
+ * Returns the words explained this way.
+ */
+fn report_homoglyphs(missing: &[String]) -> Vec<String> {
+    let mut explained = Vec::new();
+    for suspicion in missing.iter().filter_map(|word| inspect(word)) {
+        if suspicion.is_mixed_script() {
+            let scripts: Vec<String> = suspicion.scripts.iter().map(|s| s.to_string()).collect();
+            yellow_ln!(
+                "{} mixes letters of several scripts : {}",
+                suspicion.word,
+                scripts.join(", ")
+            );
+        }
+        for homoglyph in suspicion.homoglyphs.iter() {
+            yellow_ln!("{} : {}", suspicion.word, homoglyph);
+        }
+        if let Some((canonical, language)) = suspicion.canonical() {
+            println!(
+                "{} : did you mean {} ({}) ?",
+                suspicion.word, canonical, language
+            );
+            explained.push(suspicion.word);
         }
     }
+    explained
+}
 
-    return (all_found, missing);
+/**
+ * Prints the dictionary words close to the ones that were not found
+ */
+fn report_suggestions(missing: &[String], wordlists: &[Box<dyn Wordlist>]) {
+    for word in missing {
+        let suggestions = suggest_all(wordlists, word);
+        if !suggestions.is_empty() {
+            println!("{} : did you mean {} ?", word, suggestions.join(", "));
+        }
+    }
 }
 
 /**
@@ -221,68 +355,153 @@ fn main() {
                 .help("Dictionaries files to use (separated with coma)")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("strict")
+                .long("strict")
+                .help("Searches words as they are, without ignoring case, punctuation and quotes")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("passphrase")
+                .short("p")
+                .long("passphrase")
+                .value_name("p")
+                .help("Passphrase used to decrypt an aezeed phrase")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("seed")
                 .index(1)
                 .help("The 24 words for seed generation"),
         )
+        .subcommand(commands::lint::subcommand())
+        .subcommand(commands::build::subcommand())
+        .subcommand(commands::collisions::subcommand())
+        .subcommand(commands::slip39::subcommand())
+        .subcommand(commands::codex32::subcommand())
+        .subcommand(commands::seedqr::subcommand())
+        .subcommand(commands::seedxor::subcommand())
+        .subcommand(commands::entropy::subcommand())
+        .subcommand(commands::bip85::subcommand())
+        .subcommand(commands::ur::subcommand())
+        .subcommand(commands::sskr::subcommand())
+        .subcommand(commands::recover::subcommand())
+        .subcommand(commands::unscramble::subcommand())
+        .subcommand(commands::passphrase::subcommand())
         .get_matches();
 
-    let seed: &str;
-    match matches.value_of("seed") {
-        Some(s) => {
-            seed = s;
-        }
+    match matches.subcommand() {
+        ("lint-dictionary", Some(sub_matches)) => exit(commands::lint::run(sub_matches)),
+        ("build-wordlist", Some(sub_matches)) => exit(commands::build::run(sub_matches)),
+        ("collisions", Some(sub_matches)) => exit(commands::collisions::run(sub_matches)),
+        ("slip39", Some(sub_matches)) => exit(commands::slip39::run(sub_matches)),
+        ("codex32", Some(sub_matches)) => exit(commands::codex32::run(sub_matches)),
+        ("seedqr", Some(sub_matches)) => exit(commands::seedqr::run(sub_matches)),
+        ("seedxor", Some(sub_matches)) => exit(commands::seedxor::run(sub_matches)),
+        ("entropy", Some(sub_matches)) => exit(commands::entropy::run(sub_matches)),
+        ("bip85", Some(sub_matches)) => exit(commands::bip85::run(sub_matches)),
+        ("ur", Some(sub_matches)) => exit(commands::ur::run(sub_matches)),
+        ("sskr", Some(sub_matches)) => exit(commands::sskr::run(sub_matches)),
+        ("recover", Some(sub_matches)) => exit(commands::recover::run(sub_matches)),
+        ("unscramble", Some(sub_matches)) => exit(commands::unscramble::run(sub_matches)),
+        ("passphrase", Some(sub_matches)) => exit(commands::passphrase::run(sub_matches)),
+        _ => {}
+    }
+
+    let seed: &str = match matches.value_of("seed") {
+        Some(s) => s,
         None => {
             red_ln!("You must provide a seed. None found.");
             exit(1);
         }
-    }
-
-    // Skips the count checker of the provided seed
+    };
 
-    let skip_count: bool = matches.is_present("skipcount");
-
-    match skip_count {
-        true => {}
-        false => match self::check_words_number(seed) {
-            Ok(_) => {}
-            Err(count) => {
-                red_ln!(
-                    "Provided mnemonic seed is invalid. 24 words expected, {} found",
-                    count
-                );
-                exit(1)
-            }
-        },
+    let tokens = tokenize(seed);
+    if !tokens.stripped.is_empty() {
+        let stripped: Vec<String> = tokens.stripped.iter().map(|c| c.to_string()).collect();
+        yellow_ln!(
+            "Invisible characters were removed from provided words : {}",
+            stripped.join(", ")
+        );
     }
 
-    let words = self::get_words(seed);
-
-    let result: (bool, Vec<String>);
+    // Skips the count checker of the provided seed.
+    // Electrum seeds, SLIP-39 shares and Monero seeds have their own
+    // lengths, checked when decoding them.
+    let length = tokens.words.len();
+    let has_own_length = electrum::seed_type(&tokens.words.join(" "))
+        .is_some_and(|seed_type| seed_type.word_counts().contains(&length))
+        || builtin_schemes().iter().any(|scheme| {
+            scheme.word_counts().contains(&length) && scheme.contains_all(&tokens.words)
+        });
+    if !matches.is_present("skipcount") && !has_own_length {
+        if let Err(error) = Mnemonic::from_words(tokens.words.clone()).check_word_count() {
+            red_ln!("{}", error);
+            exit(1)
+        }
+    }
 
     // Handles wether or not we have to use built-in dictionaries
-    match matches.value_of("dictionaries") {
+    let wordlists: Vec<Box<dyn Wordlist>> = match matches.value_of("dictionaries") {
         Some(paths_value) => {
-            let paths: Vec<&str> = self::process_paths(paths_value.trim_start());
-            result = self::check_from_external_dictionaries(paths, words);
+            let paths = process_paths(paths_value);
+            let wordlists = match load_wordlists(paths.clone()) {
+                Ok(wordlists) => wordlists,
+                Err(error) => {
+                    red_ln!("{}", error);
+                    exit(1);
+                }
+            };
+            if !commands::lint::check_dictionaries(&paths) {
+                exit(1);
+            }
+            wordlists
         }
-        None => {
-            result = self::check_from_builtin_dictionaries(words);
+        None => builtin_wordlists(),
+    };
+
+    let mut tokens = tokens;
+    if !matches.is_present("strict") {
+        let (words, corrections) = normalize_words(&tokens.words, &wordlists);
+        for correction in corrections {
+            yellow_ln!("{}", correction);
         }
+        tokens.words = words;
     }
 
-    match result {
-        (true, _) => {
+    let mnemonic = read_phrase(tokens, &wordlists);
+    println!("words to be searched : {}", mnemonic.words().join(", "));
+
+    // Other seed schemes are only looked for with the built-in dictionaries
+    let schemes = match matches.is_present("dictionaries") {
+        true => vec![],
+        false => matching_schemes(&mnemonic),
+    };
+
+    match mnemonic.check(&wordlists) {
+        Ok(()) => {
             green_ln!("Provided words were all found in dictionaries");
+            report_matching_wordlists(&mnemonic, &wordlists);
+            report_schemes(&mnemonic, &schemes);
+            report_electrum(&mnemonic, &wordlists);
+            let passphrase = matches.value_of("passphrase").unwrap_or("");
+            report_aezeed(&mnemonic, &wordlists, passphrase);
             exit(0);
         }
-        (false, missing) => {
-            let missing_str: String = missing.join(",");
-            red_ln!(
-                "One or many words were not found in dictionaries: {}",
-                missing_str
-            );
+        Err(_) if !schemes.is_empty() => {
+            report_schemes(&mnemonic, &schemes);
+            exit(0);
+        }
+        Err(error) => {
+            red_ln!("{}", error);
+            if let Error::MissingWords(missing) = error {
+                let explained = report_homoglyphs(&missing);
+                let missing: Vec<String> = missing
+                    .into_iter()
+                    .filter(|word| !explained.contains(word))
+                    .collect();
+                report_suggestions(&missing, &wordlists);
+            }
             exit(1);
         }
     }
@@ -298,6 +517,31 @@ fn test_successful_program_without_options() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+#[test]
+fn test_successful_program_reports_checksum() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant verb";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Checksum is valid with dictionary english",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_program_reports_suggestions() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "erupt quti";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("quti : did you mean quit"));
+
+    Ok(())
+}
+
 #[test]
 fn test_unsuccessful_program_without_options() -> Result<(), Box<dyn std::error::Error>> {
     let test_seed: &str = "cswisafraud quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant verb";
@@ -398,3 +642,1044 @@ fn test_successful_program_with_external_dictionary_and_skip_count(
 
     Ok(())
 }
+
+#[test]
+fn test_successful_lint_dictionary() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("lint-dictionary");
+    cmd.arg("resources/test/test_dictionary.txt");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "line 1 : unexpected whitespace in \" abaisser\"",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_lint_dictionary() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("lint-dictionary");
+    cmd.arg("resources/test/invalid_dictionary.txt");
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_program_with_invalid_external_dictionary(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "loterie batterie";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+
+    let dictionary_argument: String = format!("-d {}", "resources/test/short_dictionary.txt");
+    cmd.arg(dictionary_argument.as_str());
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "dictionary has 3 words, 2048 expected",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_program_rejects_unnormalized_external_dictionary() -> Result<(), Box<dyn std::error::Error>>
+{
+    let test_seed: &str = "loterie batterie";
+    let content = std::fs::read_to_string("resources/test/test_dictionary.txt")?;
+    let cases = [
+        ("abandon", "Abandon", "line 2 : Abandon is not lowercase"),
+        ("e\u{301}rosion", "\u{e9}rosion", "is not NFKD normalized"),
+    ];
+    for (index, (word, replacement, message)) in cases.iter().enumerate() {
+        let path = std::env::temp_dir().join(format!("b39wc_test_dictionary_{}.txt", index));
+        std::fs::write(&path, content.replacen(word, replacement, 1))?;
+        let mut cmd = Command::cargo_bin("b39wc")?;
+        cmd.arg("-d").arg(&path);
+        cmd.arg("--skip-count");
+        cmd.arg(test_seed);
+        cmd.assert()
+            .failure()
+            .stdout(predicate::str::contains(*message));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_build_wordlist() -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::env::temp_dir().join("b39wc_test_wordlist.txt");
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("build-wordlist");
+    cmd.arg("resources/test/test_corpus.txt");
+    cmd.arg("--output").arg(&output);
+    cmd.arg("--size").arg("4");
+    cmd.assert().success();
+
+    let words = std::fs::read_to_string(&output)?;
+    assert_eq!("krakou\nquarx\nve\u{301}lozo\nzorglub\n", words);
+    let source = std::fs::read_to_string(output.with_extension("rs"))?;
+    assert!(source.starts_with("pub static CUSTOM_WORDS: [&str; 4] = ["));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_build_wordlist() -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::env::temp_dir().join("b39wc_test_small_wordlist.txt");
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("build-wordlist");
+    cmd.arg("resources/test/test_corpus.txt");
+    cmd.arg("--output").arg(&output);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Corpus holds 4 words following the rules, 2048 expected",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_ambiguous_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "abandon animal angle";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Provided words could belong to 2 dictionaries",
+        ))
+        .stdout(predicate::str::contains(
+            "english and french share 100 words",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_collisions() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("collisions");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "english / french : 100 shared words",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_japanese_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "あいこくしん\u{3000}あいさつ\u{3000}あいだ";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Matching dictionaries : japanese"));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_reports_invisible_characters() -> Result<(), Box<dyn std::error::Error>>
+{
+    let test_seed: &str = "1. erupt 2. qu\u{200d}it";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Invisible characters were removed from provided words : U+200D ZERO WIDTH JOINER at position 15",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_program_reports_homoglyphs() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "erupt \u{430}bandon";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "\u{430}bandon mixes letters of several scripts : latin, cyrillic",
+        ))
+        .stdout(predicate::str::contains(
+            "U+0430 CYRILLIC SMALL LETTER A at position 1 looks like 'a'",
+        ))
+        .stdout(predicate::str::contains(
+            "\u{430}bandon : did you mean abandon (english) ?",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_numbered_grid() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "1. erupt 7. vote 13. empower 19. slide\n\
+                           2. quit 8. mixed 14. rabbit 20. correct\n\
+                           3. sphere 9. life 15. barely 21. scatter\n\
+                           4. taxi 10. elevator 16. indoor 22. deal\n\
+                           5. air 11. mammal 17. crush 23. tenant\n\
+                           6. decade 12. search 18. grid 24. verb";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Words were sorted by their numbers",
+        ))
+        .stdout(predicate::str::contains(
+            "Checksum is valid with dictionary english",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_column_major_grid() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "erupt vote empower slide\n\
+                           quit mixed rabbit correct\n\
+                           sphere life barely scatter\n\
+                           taxi elevator indoor deal\n\
+                           air mammal crush tenant\n\
+                           decade search grid verb";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Words were read column by column from a grid of 6 rows and 4 columns",
+        ))
+        .stdout(predicate::str::contains(
+            "Checksum is valid with dictionary english",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_lenient_words() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "\"Erupt quit. sphere\"";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "word 1 \"\\\"Erupt\" read as \"erupt\" (removed quotes, lowercased)",
+        ))
+        .stdout(predicate::str::contains(
+            "word 2 \"quit.\" read as \"quit\" (removed punctuation \".\")",
+        ))
+        .stdout(predicate::str::contains(
+            "Canonical phrase : erupt quit sphere",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_program_with_strict_words() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--skip-count");
+    cmd.arg("--strict");
+    cmd.arg("Erupt quit");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "One or many words were not found in dictionaries: Erupt",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_electrum_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str =
+        "bitter grass shiver impose acquire brush forget axis eager alone wine silver";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "WARNING : phrase is an Electrum segwit seed, not a BIP-39 phrase",
+    ));
+
+    let test_seed: &str = "exile viable welcome spice tackle rose oppose old wasp alpha front walk";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "WARNING : phrase is both a valid BIP-39 phrase and an Electrum standard seed",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_old_electrum_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str =
+        "hardly point goal hallway patience key stone difference ready caught listen fact";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Matching dictionaries : electrum_old",
+        ))
+        .stdout(predicate::str::contains(
+            "Phrase is a valid old Electrum seed, hex seed : 8edad31a95e7d59f8837667510d75a4d",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_program_rejects_words_of_other_schemes() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant hallway";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Matching dictionaries").not());
+
+    Ok(())
+}
+
+#[test]
+fn test_program_checks_word_count_of_other_schemes() -> Result<(), Box<dyn std::error::Error>> {
+    // Both words are in the dictionary of old Electrum seeds,
+    // which still have 12 or 24 words
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("love time");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("2 found"));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_program_with_slip39_share() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Matching dictionaries : slip39"))
+        .stdout(predicate::str::contains(
+            "Phrase is a valid SLIP-39 share : identifier 7945, group 1 of 1 (1 needed), member 1 (1 needed)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_slip39() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("slip39");
+    cmd.arg("--passphrase");
+    cmd.arg("TREZOR");
+    cmd.arg("shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed");
+    cmd.arg("shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Share 2 is valid"))
+        .stdout(predicate::str::contains(
+            "Master secret : b43ceb7e57a0ea8766221624d01b0864",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_slip39() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("slip39");
+    cmd.arg("shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "1 shares of group 1 provided, 2 needed",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("slip39");
+    cmd.arg("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Share 1 is invalid : Checksum of the share is invalid",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_codex32() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("codex32");
+    cmd.arg("MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM");
+    cmd.arg("MS12NAMECACDEFGHJKLMNPQRSTUVWXYZ023FTR2GDZMPY6PN");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Share 2 is valid : threshold 2, identifier name, share index c",
+        ))
+        .stdout(predicate::str::contains(
+            "Master seed : d1808e096b35b209ca12132b264662a5",
+        ))
+        .stdout(predicate::str::contains("BIP-39 phrase (english) : spice"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("codex32");
+    cmd.arg("--language");
+    cmd.arg("french");
+    cmd.arg("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlw");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("BIP-39 phrase (french) :"));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_codex32() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("codex32");
+    cmd.arg("MS12NAMEA320ZYXWVUTSRQPNMLKJHGFEDCAXRPP870HKKQRM");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "1 shares provided, 2 needed to recover the secret",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("codex32");
+    cmd.arg("ms10testsxxxxxxxxxxxxxxxxxxxxxxxxxx4nzvca9cmczlx");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Share 1 is invalid : Checksum of the codex32 string is invalid",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_seedqr() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter theme error hybrid van cereal salon goddess expire";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("seedqr");
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Standard payload : 011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+        ))
+        .stdout(predicate::str::contains(
+            "Compact payload : 0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("seedqr");
+    cmd.arg("0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "SeedQR holds a valid phrase : {}",
+            test_seed
+        )));
+
+    let path = std::env::temp_dir().join("b39wc_cli_seedqr.png");
+    let path = path.to_str().unwrap();
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "seedqr",
+        "--compact",
+        "--format",
+        "png",
+        "--output",
+        path,
+        test_seed,
+    ]);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["seedqr", "--image", path]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(test_seed));
+    std::fs::remove_file(path)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_seedqr() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("seedqr");
+    cmd.arg("011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010644");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Checksum of the phrase is invalid",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_seedxor() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["seedxor", "combine"]);
+    cmd.arg("romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room");
+    cmd.arg("lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth lounge");
+    cmd.arg("vault nominee cradle silk own frown throw leg cactus recall talent worry gadget surface shy planet purpose coffee drip few seven term squeeze educate");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Part 3 is valid (english)"))
+        .stdout(predicate::str::contains(
+            "Combined phrase : silent toe meat possible chair blossom wait occur this worth option bag nurse find fish scene bench asthma bike wage world quit primary indoor",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["seedxor", "split", "--parts", "4"]);
+    cmd.arg("erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant verb");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Part 4 : "));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_seedxor() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["seedxor", "combine"]);
+    cmd.arg("romance wink lottery autumn shop bring dawn tongue range crater truth ability miss spice fitness easy legal release recall obey exchange recycle dragon room");
+    cmd.arg("lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager fringe sick camera series noodle toy crowd jeans select depth depth");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Part 2 is invalid : Checksum of the phrase is invalid",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["seedxor", "split", "--parts", "three"]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("isn't a valid value"));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_entropy() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["entropy", "--words", "12"]);
+    cmd.arg("123456".repeat(9));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "54 dice rolls read : 139.6 bits of entropy",
+        ))
+        .stdout(predicate::str::contains(
+            "Phrase (english) : universe intact render tank net oval paddle thought trick movie chimney bullet",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["entropy", "--source", "coins", "--words", "12"]);
+    cmd.arg("ht".repeat(64));
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("128 coin flips read"));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_entropy() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("entropy");
+    cmd.arg("123456".repeat(9));
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "140 bits of entropy provided, 256 needed",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["entropy", "--source", "cards", "as kd as"]);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Card as was drawn twice"));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_bip85() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["bip85", "--words", "12", test_seed]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "prosper short ramp prepare exchange stove life snack client enough purpose fold",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "bip85",
+        "--application",
+        "base85",
+        "--length",
+        "12",
+        test_seed,
+    ]);
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_bip85() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["bip85", "--application", "hex", "--length", "8", test_seed]);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Length 8 is invalid for HEX, 16 to 64 expected",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["bip85", "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"]);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Checksum of the phrase is invalid",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_program_decrypts_aezeed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "absorb original enlist once climb erode kid thrive kitchen giant define tube orange leader harbor comfort olive fatal success suggest drink penalty chimney ritual";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "WARNING : phrase is an LND aezeed, not a BIP-39 phrase",
+        ))
+        .stdout(predicate::str::contains(
+            "Aezeed decrypted : version 0, wallet birthday 2018-03-22 (day 3365)",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_program_decrypts_aezeed_with_passphrase() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "absorb century submit father path glove gloom super divert garden ice mirror wisdom grass dice kit ugly castle success suggest drink monster congress flight";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Aezeed phrase can't be decrypted, the passphrase may be wrong",
+        ))
+        .stdout(predicate::str::contains("Use --passphrase"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg("--passphrase").arg("!very_safe_55345_password*");
+    cmd.arg(test_seed);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "wallet birthday 2018-03-22 (day 3365)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_program_checks_monero_seed() -> Result<(), Box<dyn std::error::Error>> {
+    let test_seed: &str = "washing thirsty occur lectures tuesday fainted toxic adapt abnormal memoir nylon mostly building shrugged online ember northern ruby woes dauntless boil family illness inroads northern";
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Matching dictionaries : monero_english",
+        ))
+        .stdout(predicate::str::contains(
+            "Phrase is a valid Monero seed with dictionary monero_english",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.arg(test_seed.replace("inroads northern", "inroads ruby"));
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Checksum word of the Monero seed is invalid, northern expected",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_ur() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["ur", "encode"]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "ur:crypto-seed/oyadgdaeaeaeaeaeaeaeaeaeaeaeaeaeaeaeaebbftpmcw",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["ur", "encode", "--type", "bip39", "--max-fragment", "30"]);
+    cmd.arg("legal winner thank year wave sausage worth useful legal winner thank yellow");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "ur:crypto-bip39/3-3/lpaxaxcsgucykelyhdamhdceiohsjziyktinjtjtihjpihjyishsjtjeiykkihjzjzjlktaoidihjtaeiesedtuo",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["ur", "decode"]);
+    cmd.arg("ur:crypto-bip39/3-3/lpaxaxcsgucykelyhdamhdceiohsjziyktinjtjtihjpihjyishsjtjeiykkihjzjzjlktaoidihjtaeiesedtuo");
+    cmd.arg("UR:CRYPTO-BIP39/1-3/LPADAXCSGUCYKELYHDAMHDCEOEADLKIHJZIHIOHSJZIYKTINJTJTIHJPIHJYISHSJTJEIEKKIHHSJPIEAYVENTBN");
+    cmd.arg("ur:crypto-bip39/2-3/lpaoaxcsgucykelyhdamhdcekthskoihiojkhskpjkhsioihihktjljpjyisiykpjkihiykpjzihjzihfshegdnd");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "UR holds a valid phrase (english) : legal winner thank year wave sausage worth useful legal winner thank yellow",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_ur() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["ur", "decode"]);
+    cmd.arg("ur:crypto-seed/oyadgdaeaeaeaeaeaeaeaeaeaeaeaeaeaeaeaebbftpmcx");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Checksum of the bytewords is invalid",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["ur", "decode"]);
+    cmd.arg("ur:crypto-bip39/1-3/lpadaxcsgucykelyhdamhdceoeadlkihjzihiohsjziyktinjtjtihjpihjyishsjtjeiekkihhsjpieayventbn");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "1 fragments of 3 decoded, more parts are needed",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_sskr() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["sskr", "combine", "--language", "french"]);
+    cmd.arg("tuna acid epic gyro good tomb acid acid able bald crux lazy navy iris claw loud half monk urge iron meow many very silk holy time love wolf lava");
+    cmd.arg("tuna acid epic gyro good tomb acid acid also limp kite rock noon join exit iris work wasp redo gush ugly zone cats next deli barn foxy fish paid");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Share 2 is valid : identifier 20694, group 1 of 2 (1 needed), member 3 (2 needed)",
+        ))
+        .stdout(predicate::str::contains(
+            "Phrase (french) : implorer visage sonnette voyage ",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "sskr",
+        "split",
+        "--group",
+        "2-of-3",
+        "--group",
+        "3-of-5",
+        "--group-threshold",
+        "2",
+    ]);
+    cmd.arg("legal winner thank year wave sausage worth useful legal winner thank yellow");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Group 2 share 5 : tuna acid epic"));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_sskr() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["sskr", "combine"]);
+    cmd.arg("tuna acid epic gyro good tomb acid acid able bald crux lazy navy iris claw loud half monk urge iron meow many very silk holy time love wolf lava");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "1 shares of group 1 provided, 2 needed",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["sskr", "split", "--group", "3-of-2"]);
+    cmd.arg("legal winner thank year wave sausage worth useful legal winner thank yellow");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Group 3-of-2 is invalid"));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_recover() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "recover",
+        "--target",
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+    ]);
+    cmd.arg(
+        "abandon ? abandon abandon abandon abandon abandon abandon abandon abandon abandon ab*",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Searching 20480 phrases for words [2, 12]"))
+        .stdout(predicate::str::contains(
+            "Match : abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["recover", "-t", "73c5da0a", "--threads", "2"]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abo*|zoo");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Match : abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_recover() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["recover", "-t", "73c5da0b"]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abo*");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("No phrase matches the target"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["recover", "-t", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabB"]);
+    cmd.arg(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ?",
+    );
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabB",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["recover", "-t", "73c5da0a"]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon xyz*");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Word not found in dictionary : xyz*",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["recover", "-t", "73c5da0a"]);
+    cmd.arg("? ? ? ? abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "This search would take weeks or more, give more words or use --force",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_unscramble() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["unscramble", "--target", "73c5da0a"]);
+    cmd.args([
+        "-f", "2", "-f", "3", "-f", "4", "-f", "5", "-f", "6", "-f", "7", "-f", "8",
+    ]);
+    cmd.arg("about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Searching 120 orders of the words"))
+        .stdout(predicate::str::contains(
+            "Match : abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["unscramble", "--limit", "2"]);
+    cmd.arg("legal winner thank year wave sausage worth useful legal winner thank yellow");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Searching 479001600 orders of the words"))
+        .stdout(predicate::str::contains(
+            "Valid order : legal legal sausage thank thank useful wave winner winner yellow worth year",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_unscramble() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["unscramble", "--target", "73c5da0a"]);
+    cmd.arg("about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "This search would take weeks or more, fix more positions or use --force",
+    ));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args(["unscramble", "--fixed", "13"]);
+    cmd.arg("about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Position 13 is invalid, positions go from 1 to 12",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("b39wc-cli-checkpoint-{}", std::process::id()));
+    let path = path.to_str().unwrap();
+    let phrase =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ab*";
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "recover",
+        "-t",
+        "73c5da0b",
+        "-p",
+        "secret",
+        "--checkpoint",
+        path,
+    ]);
+    cmd.arg(phrase);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("No phrase matches the target"));
+    let saved = std::fs::read_to_string(path)?;
+    assert!(saved.contains("position=10\n"));
+    assert!(!saved.contains("abandon") && !saved.contains("secret"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "recover",
+        "-t",
+        "73c5da0b",
+        "-p",
+        "secret",
+        "--checkpoint",
+        path,
+    ]);
+    cmd.arg(phrase);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Resuming from candidate 10 of 10"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "recover",
+        "-t",
+        "73c5da0b",
+        "-p",
+        "other",
+        "--checkpoint",
+        path,
+    ]);
+    cmd.arg(phrase);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "was saved by another search, its parameters differ",
+    ));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_checkpoint_after_match() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("b39wc-cli-match-{}", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    // Resuming a search finds its match again instead of going past it
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("b39wc")?;
+        cmd.args(["recover", "-t", "73c5da0a", "--checkpoint", path]);
+        cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ab*");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Match : abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            ))
+            .stdout(predicate::str::contains("No phrase matches the target").not());
+    }
+    std::fs::remove_file(path)?;
+
+    let passphrases = std::env::temp_dir().join(format!("b39wc-match-list-{}", std::process::id()));
+    std::fs::write(&passphrases, "bitcoin\ntrezor\n")?;
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("b39wc")?;
+        cmd.args([
+            "passphrase",
+            "--target",
+            "b4e3f5ed",
+            "--digits",
+            "0",
+            "--candidates",
+        ]);
+        cmd.arg(&passphrases);
+        cmd.args(["--checkpoint", path]);
+        cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("Passphrase : \"TREZOR\""));
+    }
+    std::fs::remove_file(path)?;
+    std::fs::remove_file(&passphrases)?;
+    Ok(())
+}
+
+#[test]
+fn test_successful_passphrase() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("b39wc-passphrases-{}", std::process::id()));
+    std::fs::write(&path, "bitcoin\ntrezor\n")?;
+
+    // Master fingerprint of the phrase protected by TREZOR
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "passphrase",
+        "--target",
+        "b4e3f5ed",
+        "--digits",
+        "0",
+        "--candidates",
+    ]);
+    cmd.arg(&path);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Trying 112 passphrases against the master fingerprint",
+        ))
+        .stdout(predicate::str::contains("Passphrase : \"TREZOR\""));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_passphrase() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("b39wc-no-passphrases-{}", std::process::id()));
+    std::fs::write(&path, "trezor\n")?;
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "passphrase",
+        "--target",
+        "b4e3f5ed",
+        "--no-typos",
+        "--candidates",
+    ]);
+    cmd.arg(&path);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Trying 1 passphrases"))
+        .stdout(predicate::str::contains("No passphrase matches the target"));
+
+    let mut cmd = Command::cargo_bin("b39wc")?;
+    cmd.args([
+        "passphrase",
+        "--target",
+        "b4e3f5ed",
+        "--candidates",
+        "missing.txt",
+    ]);
+    cmd.arg("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Error loading passphrases with path : missing.txt",
+    ));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
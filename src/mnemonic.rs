@@ -0,0 +1,384 @@
+use crate::error::Error;
+use crate::language::Language;
+use crate::tokenizer::tokenize;
+use crate::wordlist::Wordlist;
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * A mnemonic phrase, split into words
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    words: Vec<String>,
+}
+
+impl Mnemonic {
+    /**
+     * The official mnemonic phrase length
+     */
+    pub const WORD_COUNT: usize = 24;
+
+    /**
+     * The phrase lengths allowed by BIP-39, each holding a checksum
+     */
+    pub const VALID_LENGTHS: [usize; 5] = [12, 15, 18, 21, 24];
+
+    /**
+     * The number of words of a dictionary usable for BIP-39
+     */
+    pub const WORDLIST_SIZE: usize = 2048;
+
+    /**
+     * Splits the seed string into seperated words.
+     * See `tokenizer::tokenize` for the accepted separators.
+     */
+    pub fn parse(phrase: &str) -> Mnemonic {
+        Mnemonic {
+            words: tokenize(phrase).words,
+        }
+    }
+
+    /**
+     * Builds a mnemonic from already separated words
+     */
+    pub fn from_words(words: Vec<String>) -> Mnemonic {
+        Mnemonic { words }
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /**
+     * Checks the number of words of the phrase.
+     * Fails if phrase has more or less words than 24
+     * which is the official mnemonic phrase length
+     */
+    pub fn check_word_count(&self) -> Result<(), Error> {
+        if self.len() != Mnemonic::WORD_COUNT {
+            return Err(Error::WordCount {
+                expected: Mnemonic::WORD_COUNT,
+                found: self.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /**
+     * Returns the words that can't be found in any of the wordlists
+     */
+    pub fn missing_words(&self, wordlists: &[Box<dyn Wordlist>]) -> Vec<String> {
+        self.words
+            .iter()
+            .filter(|word| !wordlists.iter().any(|wordlist| wordlist.contains(word)))
+            .cloned()
+            .collect()
+    }
+
+    /**
+     * Checks that every word of the phrase is part of at least one wordlist
+     */
+    pub fn check(&self, wordlists: &[Box<dyn Wordlist>]) -> Result<(), Error> {
+        let missing = self.missing_words(wordlists);
+        if !missing.is_empty() {
+            return Err(Error::MissingWords(missing));
+        }
+        Ok(())
+    }
+
+    /**
+     * Returns the dictionaries containing every word of the phrase
+     */
+    pub fn matching_wordlists<'a>(
+        &self,
+        wordlists: &'a [Box<dyn Wordlist>],
+    ) -> Vec<&'a dyn Wordlist> {
+        wordlists
+            .iter()
+            .filter(|wordlist| self.words.iter().all(|word| wordlist.contains(word)))
+            .map(|wordlist| wordlist.as_ref())
+            .collect()
+    }
+
+    /**
+     * Returns the index of each word of the phrase in a dictionary
+     */
+    pub fn indexes(&self, wordlist: &dyn Wordlist) -> Result<Vec<usize>, Error> {
+        self.words
+            .iter()
+            .map(|word| {
+                wordlist
+                    .index_of(word)
+                    .ok_or_else(|| Error::UnknownWord(word.clone()))
+            })
+            .collect()
+    }
+
+    /**
+     * Decodes the entropy held by the phrase.
+     * Fails if the checksum held by the last word does not match.
+     */
+    pub fn to_entropy(&self, wordlist: &dyn Wordlist) -> Result<Vec<u8>, Error> {
+        check_wordlist_size(wordlist)?;
+        if !Mnemonic::VALID_LENGTHS.contains(&self.len()) {
+            return Err(Error::ChecksumLength(self.len()));
+        }
+
+        Mnemonic::entropy_of_indexes(&self.indexes(wordlist)?)
+    }
+
+    /**
+     * Decodes the entropy held by the dictionary indexes of the words
+     * of a phrase, which must have a valid length.
+     * Fails if the checksum held by the last index does not match.
+     */
+    pub fn entropy_of_indexes(indexes: &[usize]) -> Result<Vec<u8>, Error> {
+        let mut bits: Vec<bool> = Vec::with_capacity(indexes.len() * 11);
+        for index in indexes {
+            bits.extend((0..11).rev().map(|shift| (index >> shift) & 1 == 1));
+        }
+
+        let checksum_length = bits.len() / 33;
+        let (entropy_bits, checksum_bits) = bits.split_at(bits.len() - checksum_length);
+        let entropy: Vec<u8> = entropy_bits.chunks(8).map(bits_to_byte).collect();
+
+        if checksum(&entropy, checksum_length) != checksum_bits {
+            return Err(Error::InvalidChecksum);
+        }
+
+        Ok(entropy)
+    }
+
+    /**
+     * Checks that the last word of the phrase holds a valid checksum
+     */
+    pub fn verify_checksum(&self, wordlist: &dyn Wordlist) -> Result<(), Error> {
+        self.to_entropy(wordlist).map(|_| ())
+    }
+
+    /**
+     * Writes the phrase as expected by a dictionary.
+     * Japanese words are separated by ideographic spaces.
+     */
+    pub fn to_phrase(&self, wordlist: &dyn Wordlist) -> String {
+        let separator = match wordlist.language() {
+            Some(Language::Japanese) => "\u{3000}",
+            _ => " ",
+        };
+        self.words.join(separator)
+    }
+
+    /**
+     * Checks if the checksum of the phrase is valid with
+     * at least one of the dictionaries holding all its words
+     */
+    pub fn has_valid_checksum(&self, wordlists: &[Box<dyn Wordlist>]) -> bool {
+        self.matching_wordlists(wordlists)
+            .into_iter()
+            .any(|wordlist| self.verify_checksum(wordlist).is_ok())
+    }
+
+    /**
+     * Builds the phrase encoding some entropy, checksum included.
+     * Entropy must be 16, 20, 24, 28 or 32 bytes long.
+     */
+    pub fn from_entropy(entropy: &[u8], wordlist: &dyn Wordlist) -> Result<Mnemonic, Error> {
+        check_wordlist_size(wordlist)?;
+        if entropy.len() < 16 || entropy.len() > 32 || !entropy.len().is_multiple_of(4) {
+            return Err(Error::EntropyLength(entropy.len()));
+        }
+
+        let mut bits: Vec<bool> = entropy
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1 == 1))
+            .collect();
+        bits.extend(checksum(entropy, entropy.len() / 4));
+
+        let words = bits
+            .chunks(11)
+            .map(|chunk| {
+                let index = chunk.iter().fold(0, |acc, &bit| (acc << 1) | bit as usize);
+                wordlist.word(index).unwrap_or_default().to_string()
+            })
+            .collect();
+
+        Ok(Mnemonic { words })
+    }
+
+    /**
+     * Derives the 64 bytes seed of the phrase, protected by a passphrase.
+     * Fails if the checksum of the phrase is invalid.
+     */
+    pub fn to_seed(&self, wordlist: &dyn Wordlist, passphrase: &str) -> Result<[u8; 64], Error> {
+        self.verify_checksum(wordlist)?;
+        let phrase: String = self.words.join(" ").nfkd().collect();
+        let salt: String = format!("mnemonic{}", passphrase).nfkd().collect();
+        let mut seed = [0u8; 64];
+        pbkdf2_hmac::<Sha512>(phrase.as_bytes(), salt.as_bytes(), 2048, &mut seed);
+        Ok(seed)
+    }
+}
+
+/**
+ * Fails if a dictionary can't be used to encode entropy
+ */
+fn check_wordlist_size(wordlist: &dyn Wordlist) -> Result<(), Error> {
+    if wordlist.len() != Mnemonic::WORDLIST_SIZE {
+        return Err(Error::WordlistSize {
+            name: wordlist.name(),
+            size: wordlist.len(),
+        });
+    }
+    Ok(())
+}
+
+/**
+ * Returns the first bits of the SHA-256 hash of the entropy
+ */
+fn checksum(entropy: &[u8], length: usize) -> Vec<bool> {
+    let hash = Sha256::digest(entropy);
+    (0..length)
+        .map(|i| (hash[i / 8] >> (7 - i % 8)) & 1 == 1)
+        .collect()
+}
+
+fn bits_to_byte(bits: &[bool]) -> u8 {
+    bits.iter().fold(0, |acc, &bit| (acc << 1) | bit as u8)
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.words.join(" "))
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    fn from_str(phrase: &str) -> Result<Mnemonic, Error> {
+        Ok(Mnemonic::parse(phrase))
+    }
+}
+
+#[cfg(test)]
+use crate::wordlist::builtin_wordlists;
+
+#[test]
+fn test_parse() {
+    let mnemonic = Mnemonic::parse("abandon toto tata");
+    let results = mnemonic.words();
+    assert_eq!("abandon", results[0]);
+    assert_eq!("toto", results[1]);
+    assert_ne!("abandon", results[1]);
+    assert_eq!("tata", results[2]);
+    assert_eq!("abandon toto tata", mnemonic.to_string());
+
+    let mnemonic = Mnemonic::parse("1. abandon\u{3000}2. toto,\u{200b}tata");
+    assert_eq!(vec!["abandon", "toto", "tata"], mnemonic.words());
+}
+
+#[test]
+fn test_check_word_count() {
+    let test_seed: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid slide correct scatter deal tenant verb";
+    let test_seed_failure: &str = "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower rabbit barely indoor crush grid";
+    assert!(Mnemonic::parse(test_seed).check_word_count().is_ok());
+    assert_eq!(
+        Err(Error::WordCount {
+            expected: 24,
+            found: 18
+        }),
+        Mnemonic::parse(test_seed_failure).check_word_count()
+    );
+}
+
+#[test]
+fn test_check() {
+    let wordlists = builtin_wordlists();
+    let mnemonic = Mnemonic::parse("erupt quit cswisafraud sphere");
+    assert_eq!(
+        Err(Error::MissingWords(vec!["cswisafraud".to_string()])),
+        mnemonic.check(&wordlists)
+    );
+    assert!(Mnemonic::parse("erupt quit sphere")
+        .check(&wordlists)
+        .is_ok());
+}
+
+#[test]
+fn test_matching_wordlists() {
+    let wordlists = builtin_wordlists();
+    let mnemonic = Mnemonic::parse("erupt quit sphere");
+    let matching = mnemonic.matching_wordlists(&wordlists);
+    assert_eq!(1, matching.len());
+    assert_eq!(Some(Language::English), matching[0].language());
+}
+
+#[test]
+fn test_entropy() {
+    let mnemonic = Mnemonic::parse(
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    );
+    let entropy = mnemonic.to_entropy(&Language::English).unwrap();
+    assert_eq!(vec![0x7f; 16], entropy);
+    assert_eq!(
+        mnemonic,
+        Mnemonic::from_entropy(&entropy, &Language::English).unwrap()
+    );
+
+    let mnemonic = Mnemonic::parse("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote");
+    assert_eq!(
+        vec![0xff; 32],
+        mnemonic.to_entropy(&Language::English).unwrap()
+    );
+
+    let mnemonic = Mnemonic::parse(
+        "legal winner thank year wave sausage worth useful legal winner thank thank",
+    );
+    assert_eq!(
+        Err(Error::InvalidChecksum),
+        mnemonic.verify_checksum(&Language::English)
+    );
+
+    assert_eq!(
+        Err(Error::EntropyLength(15)),
+        Mnemonic::from_entropy(&[0; 15], &Language::English)
+    );
+}
+
+#[test]
+fn test_to_seed() {
+    let mnemonic = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+    );
+    let seed = mnemonic.to_seed(&Language::English, "TREZOR").unwrap();
+    assert_eq!(
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        crate::encoding::to_hex(&seed)
+    );
+}
+
+#[test]
+fn test_to_phrase() {
+    let mnemonic = Mnemonic::parse("あいこくしん あいさつ");
+    assert_eq!(
+        "あいこくしん\u{3000}あいさつ",
+        mnemonic.to_phrase(&Language::Japanese)
+    );
+    assert_eq!(
+        "erupt quit",
+        Mnemonic::parse("erupt quit").to_phrase(&Language::English)
+    );
+}
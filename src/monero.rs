@@ -0,0 +1,243 @@
+use crate::dictionaries::monero;
+use crate::encoding::crc32;
+use crate::error::Error;
+use crate::language::Language;
+use crate::wordlist::Wordlist;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/**
+ * Number of words encoding the key of a Monero seed,
+ * followed by a checksum word
+ */
+pub const SEED_WORDS: usize = 24;
+
+/**
+ * Dictionaries of Monero seeds.
+ * Their 1626 words encode the private spend key in base 1626,
+ * and each word is identified by its first letters only.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoneroWordlist {
+    English,
+}
+
+impl MoneroWordlist {
+    pub const ALL: [MoneroWordlist; 1] = [MoneroWordlist::English];
+
+    pub const SIZE: usize = 1626;
+
+    /**
+     * Returns the words of the dictionary
+     */
+    pub fn words(self) -> &'static [&'static str; 1626] {
+        match self {
+            MoneroWordlist::English => &monero::ENGLISH,
+        }
+    }
+
+    /**
+     * Returns the number of letters identifying a word
+     */
+    pub fn prefix_length(self) -> usize {
+        match self {
+            MoneroWordlist::English => 3,
+        }
+    }
+
+    /**
+     * Returns the pairs of words sharing the same prefix,
+     * which must not exist for words to be identified by their prefix
+     */
+    pub fn prefix_collisions(self) -> Vec<(&'static str, &'static str)> {
+        let mut seen: HashMap<String, &'static str> = HashMap::new();
+        let mut collisions = Vec::new();
+        for &word in self.words().iter() {
+            if let Some(previous) = seen.insert(prefix(word, self.prefix_length()), word) {
+                collisions.push((previous, word));
+            }
+        }
+        collisions
+    }
+
+    /**
+     * Returns the index of the word starting with the prefix of a word
+     */
+    pub fn index_of_prefix(self, word: &str) -> Option<usize> {
+        prefix_indexes()[&self]
+            .get(&prefix(word, self.prefix_length()))
+            .copied()
+    }
+}
+
+impl Wordlist for MoneroWordlist {
+    fn name(&self) -> String {
+        match self {
+            MoneroWordlist::English => "monero_english".to_string(),
+        }
+    }
+
+    fn language(&self) -> Option<Language> {
+        match self {
+            MoneroWordlist::English => Some(Language::English),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words().len()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words().get(index).copied()
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        self.index_of_prefix(word)
+            .filter(|&index| self.words()[index] == word)
+    }
+}
+
+fn prefix(word: &str, length: usize) -> String {
+    word.chars().take(length).collect()
+}
+
+/**
+ * Returns the index of the prefix of every word of the Monero dictionaries,
+ * built on first use
+ */
+fn prefix_indexes() -> &'static HashMap<MoneroWordlist, HashMap<String, usize>> {
+    static INDEXES: OnceLock<HashMap<MoneroWordlist, HashMap<String, usize>>> = OnceLock::new();
+    INDEXES.get_or_init(|| {
+        MoneroWordlist::ALL
+            .iter()
+            .map(|&wordlist| {
+                let indexes = wordlist
+                    .words()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| (prefix(word, wordlist.prefix_length()), index))
+                    .collect();
+                (wordlist, indexes)
+            })
+            .collect()
+    })
+}
+
+/**
+ * Returns the checksum word of the 24 first words of a seed : one of them,
+ * chosen by the CRC-32 of their prefixes
+ */
+pub fn checksum_word(words: &[String], wordlist: MoneroWordlist) -> String {
+    let words = &words[..SEED_WORDS.min(words.len())];
+    let prefixes: String = words
+        .iter()
+        .map(|word| prefix(word, wordlist.prefix_length()))
+        .collect();
+    let index = crc32(prefixes.as_bytes()) as usize % words.len().max(1);
+    words.get(index).cloned().unwrap_or_default()
+}
+
+/**
+ * Decodes the private spend key held by a Monero seed of 25 words,
+ * or 24 words without checksum. Every three words encode four bytes
+ * of the key, and words are identified by their prefix.
+ */
+pub fn decode_seed(words: &[String], wordlist: MoneroWordlist) -> Result<Vec<u8>, Error> {
+    if words.len() != SEED_WORDS && words.len() != SEED_WORDS + 1 {
+        return Err(Error::MoneroSeedLength(words.len()));
+    }
+
+    let indexes: Vec<u64> = words
+        .iter()
+        .map(|word| {
+            wordlist
+                .index_of_prefix(word)
+                .map(|index| index as u64)
+                .ok_or_else(|| Error::UnknownWord(word.clone()))
+        })
+        .collect::<Result<_, _>>()?;
+
+    if let Some(last) = words.get(SEED_WORDS) {
+        let expected = checksum_word(words, wordlist);
+        let length = wordlist.prefix_length();
+        if prefix(last, length) != prefix(&expected, length) {
+            return Err(Error::InvalidMoneroChecksum(expected));
+        }
+    }
+
+    let size = MoneroWordlist::SIZE as u64;
+    let mut key = Vec::with_capacity(32);
+    for (triple, indexes) in words.chunks(3).zip(indexes[..SEED_WORDS].chunks(3)) {
+        let value = indexes[0]
+            + size * ((indexes[1] + size - indexes[0]) % size)
+            + size * size * ((indexes[2] + size - indexes[1]) % size);
+        if value > u32::MAX as u64 {
+            return Err(Error::MoneroSeedOverflow(triple.join(" ")));
+        }
+        key.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+
+    Ok(key)
+}
+
+/**
+ * Encodes a private spend key of 32 bytes as a Monero seed,
+ * checksum word included
+ */
+pub fn encode_seed(key: &[u8; 32], wordlist: MoneroWordlist) -> Vec<String> {
+    let size = MoneroWordlist::SIZE as u32;
+    let mut words: Vec<String> = Vec::with_capacity(SEED_WORDS + 1);
+    for chunk in key.chunks(4) {
+        let value = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let first = value % size;
+        let second = (value / size + first) % size;
+        let third = (value / size / size + second) % size;
+        for index in [first, second, third].iter() {
+            words.push(wordlist.words()[*index as usize].to_string());
+        }
+    }
+    words.push(checksum_word(&words, wordlist));
+    words
+}
+
+#[cfg(test)]
+fn words(phrase: &str) -> Vec<String> {
+    phrase.split(' ').map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn test_monero_wordlist() {
+    assert_eq!(MoneroWordlist::SIZE, MoneroWordlist::English.len());
+    assert!(MoneroWordlist::English.prefix_collisions().is_empty());
+    assert_eq!(Some(0), MoneroWordlist::English.index_of("abbey"));
+    assert_eq!(Some(0), MoneroWordlist::English.index_of_prefix("abb"));
+    assert_eq!(None, MoneroWordlist::English.index_of("abb"));
+}
+
+#[test]
+fn test_decode_seed() {
+    use crate::encoding::to_hex;
+
+    let phrase = "washing thirsty occur lectures tuesday fainted toxic adapt abnormal memoir \
+                  nylon mostly building shrugged online ember northern ruby woes dauntless \
+                  boil family illness inroads northern";
+    let key = decode_seed(&words(phrase), MoneroWordlist::English).unwrap();
+    assert_eq!(
+        "c0af65c0dd837e666b9d0dfed62745f4df35aed7ea619b2798a709f0fe545403",
+        to_hex(&key)
+    );
+
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&key);
+    assert_eq!(words(phrase), encode_seed(&bytes, MoneroWordlist::English));
+
+    // Words may be truncated to their prefix
+    let prefixes: Vec<String> = words(phrase).iter().map(|word| prefix(word, 3)).collect();
+    assert_eq!(Ok(key), decode_seed(&prefixes, MoneroWordlist::English));
+
+    let wrong = phrase.replace("inroads northern", "inroads ruby");
+    assert_eq!(
+        Err(Error::InvalidMoneroChecksum("northern".to_string())),
+        decode_seed(&words(&wrong), MoneroWordlist::English)
+    );
+}
@@ -0,0 +1,304 @@
+use crate::base58;
+use crate::bip32::{hash160, ExtendedKey, ExtendedPublicKey, ScriptType};
+use crate::encoding::from_hex;
+use crate::error::Error;
+use std::fmt;
+
+/**
+ * Characters of bech32, in the order of their values
+ */
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/**
+ * Human readable part of mainnet segwit addresses
+ */
+const SEGWIT_HRP: &str = "bc";
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/**
+ * Decodes a mainnet version 0 segwit address of 20 bytes,
+ * returning the hash of its public key
+ */
+fn decode_segwit(address: &str) -> Option<[u8; 20]> {
+    if address.to_lowercase() != address && address.to_uppercase() != address {
+        return None;
+    }
+    let address = address.to_lowercase();
+    let (hrp, data) = address.rsplit_once('1')?;
+    if hrp != SEGWIT_HRP || data.len() < 7 {
+        return None;
+    }
+    let values: Vec<u8> = data
+        .chars()
+        .map(|c| BECH32_CHARSET.find(c).map(|value| value as u8))
+        .collect::<Option<_>>()?;
+
+    let expanded = hrp
+        .bytes()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|b| b & 31))
+        .chain(values.iter().copied());
+    // Version 0 uses bech32, whose checksum constant is 1
+    if values[0] != 0 || bech32_polymod(expanded) != 1 {
+        return None;
+    }
+
+    let program = &values[1..values.len() - 6];
+    let mut bytes = Vec::new();
+    let (mut accumulator, mut bits) = (0u32, 0);
+    for value in program {
+        accumulator = (accumulator << 5) | *value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+        }
+    }
+    if bits >= 5 || accumulator & ((1 << bits) - 1) != 0 || bytes.len() != 20 {
+        return None;
+    }
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&bytes);
+    Some(hash)
+}
+
+/**
+ * What a recovered phrase must match : the fingerprint of its master
+ * key, an extended public key or an address derived from it
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Fingerprint([u8; 4]),
+    ExtendedPublicKey(ExtendedPublicKey),
+    Address {
+        script_type: ScriptType,
+        hash: [u8; 20],
+    },
+}
+
+impl Target {
+    /**
+     * Reads a master fingerprint (8 hexadecimal digits), an xpub, ypub
+     * or zpub, or a mainnet P2PKH, P2SH-P2WPKH or P2WPKH address
+     */
+    pub fn parse(target: &str) -> Result<Target, Error> {
+        let target = target.trim();
+        if target.len() == 8 {
+            if let Some(bytes) = from_hex(target) {
+                return Ok(Target::Fingerprint([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                ]));
+            }
+        }
+        if let Ok(key) = ExtendedPublicKey::from_xpub(target) {
+            return Ok(Target::ExtendedPublicKey(key));
+        }
+        if let Some(hash) = decode_segwit(target) {
+            return Ok(Target::Address {
+                script_type: ScriptType::NativeSegwit,
+                hash,
+            });
+        }
+        match base58::decode_check(target) {
+            Some(bytes) if bytes.len() == 21 && (bytes[0] == 0 || bytes[0] == 5) => {
+                let mut hash = [0u8; 20];
+                hash.copy_from_slice(&bytes[1..]);
+                let script_type = match bytes[0] {
+                    0 => ScriptType::Legacy,
+                    _ => ScriptType::NestedSegwit,
+                };
+                Ok(Target::Address { script_type, hash })
+            }
+            _ => Err(Error::UnknownTarget(target.to_string())),
+        }
+    }
+}
+
+/**
+ * Checks seeds against a target. Extended public keys are derived at
+ * `path`, addresses at indexes 0 to `gap` under `path`.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Oracle {
+    target: Target,
+    path: Vec<u32>,
+    gap: u32,
+}
+
+impl Oracle {
+    /**
+     * Builds the oracle of a target. Without a path, extended public
+     * keys are checked as the first account of their script type, and
+     * addresses as the first receiving addresses of their script type.
+     */
+    pub fn new(target: Target, path: Option<Vec<u32>>, gap: u32) -> Result<Oracle, Error> {
+        let path = match (&target, path) {
+            (_, Some(path)) => path,
+            (Target::ExtendedPublicKey(key), None) if key.depth == 0 => vec![],
+            (Target::ExtendedPublicKey(key), None) => key.script_type.account_path(),
+            (Target::Address { script_type, .. }, None) => {
+                let mut path = script_type.account_path();
+                path.push(0);
+                path
+            }
+            (Target::Fingerprint(_), None) => vec![],
+        };
+        if let Target::ExtendedPublicKey(key) = &target {
+            if key.depth as usize != path.len() {
+                return Err(Error::InvalidDerivationPath(path_to_string(&path)));
+            }
+        }
+        Ok(Oracle { target, path, gap })
+    }
+
+    /**
+     * Checks if a seed matches the target
+     */
+    pub fn matches(&self, seed: &[u8]) -> bool {
+        let master = match ExtendedKey::from_seed(seed) {
+            Ok(master) => master,
+            Err(_) => return false,
+        };
+        match &self.target {
+            Target::Fingerprint(fingerprint) => master.fingerprint() == *fingerprint,
+            Target::ExtendedPublicKey(key) => master
+                .derive_path(&self.path)
+                .map(|derived| key.matches(&derived))
+                .unwrap_or(false),
+            Target::Address { script_type, hash } => {
+                let parent = match master.derive_path(&self.path) {
+                    Ok(parent) => parent,
+                    Err(_) => return false,
+                };
+                (0..self.gap).any(|index| {
+                    parent
+                        .derive(index)
+                        .map(|child| address_hash(*script_type, &child.public_key()) == *hash)
+                        .unwrap_or(false)
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for Oracle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.target {
+            Target::Fingerprint(_) => write!(f, "master fingerprint"),
+            Target::ExtendedPublicKey(_) => {
+                write!(f, "extended public key at {}", path_to_string(&self.path))
+            }
+            Target::Address { .. } => write!(
+                f,
+                "address among {}/0 to {}/{}",
+                path_to_string(&self.path),
+                path_to_string(&self.path),
+                self.gap.saturating_sub(1)
+            ),
+        }
+    }
+}
+
+/**
+ * Returns the hash an address of a script type holds for a public key
+ */
+fn address_hash(script_type: ScriptType, public_key: &[u8; 33]) -> [u8; 20] {
+    let key_hash = hash160(public_key);
+    match script_type {
+        ScriptType::NestedSegwit => {
+            let mut script = vec![0x00, 0x14];
+            script.extend_from_slice(&key_hash);
+            hash160(&script)
+        }
+        _ => key_hash,
+    }
+}
+
+fn path_to_string(path: &[u32]) -> String {
+    std::iter::once("m".to_string())
+        .chain(
+            path.iter()
+                .map(|index| match index & crate::bip32::HARDENED {
+                    0 => index.to_string(),
+                    _ => format!("{}'", index & !crate::bip32::HARDENED),
+                }),
+        )
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+#[cfg(test)]
+fn test_seed() -> [u8; 64] {
+    use crate::language::Language;
+    use crate::mnemonic::Mnemonic;
+
+    Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+    )
+    .to_seed(&Language::English, "")
+    .unwrap()
+}
+
+#[test]
+fn test_targets() {
+    // Vectors of BIP-44, BIP-49 and BIP-84 for the same phrase
+    let seed = test_seed();
+    let targets = [
+        "73c5da0a",
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+        "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+        "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP",
+        "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+    ];
+    for target in targets.iter() {
+        let oracle = Oracle::new(Target::parse(target).unwrap(), None, 1).unwrap();
+        assert!(oracle.matches(&seed), "{}", target);
+        assert!(!oracle.matches(&[0; 64]), "{}", target);
+    }
+
+    // Second receiving address of BIP-84
+    let target = Target::parse("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g").unwrap();
+    assert!(!Oracle::new(target.clone(), None, 1).unwrap().matches(&seed));
+    assert!(Oracle::new(target, None, 2).unwrap().matches(&seed));
+}
+
+#[test]
+fn test_invalid_targets() {
+    for target in [
+        "73c5da0",
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyv",
+        "bc1qcR8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabB",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Err(Error::UnknownTarget(target.to_string())),
+            Target::parse(target)
+        );
+    }
+
+    let target = Target::parse("zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs").unwrap();
+    assert_eq!(
+        Err(Error::InvalidDerivationPath("m/84'".to_string())),
+        Oracle::new(target, Some(vec![84 | crate::bip32::HARDENED]), 1)
+    );
+}
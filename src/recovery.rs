@@ -0,0 +1,554 @@
+use crate::error::Error;
+use crate::mnemonic::Mnemonic;
+use crate::oracle::Oracle;
+use crate::search::{self, Progress};
+use crate::wordlist::Wordlist;
+
+/**
+ * Number of candidates above which a search is considered infeasible,
+ * when only their checksum is checked : weeks on a desktop computer
+ */
+pub const MAX_CANDIDATES: u128 = 1 << 44;
+
+/**
+ * Number of seeds above which a search is considered infeasible, each
+ * of them costing 2048 rounds of PBKDF2 : weeks on a desktop computer
+ */
+pub const MAX_SEEDS: u128 = 1 << 34;
+
+/**
+ * Numbered phrases, some of which may be skipped
+ */
+pub trait Candidates: Sync {
+    /**
+     * Returns the number of candidates, saturated at the largest number
+     */
+    fn count(&self) -> u128;
+
+    /**
+     * Returns the word indexes of the phrase of a number, or None
+     * if the number is skipped
+     */
+    fn candidate(&self, number: u128) -> Option<Vec<usize>>;
+
+    /**
+     * Returns the number of words of the phrases
+     */
+    fn word_count(&self) -> usize;
+
+    /**
+     * Estimates the number of candidates with a valid checksum, whose
+     * seed must be derived. The checksum holds one bit every 3 words.
+     */
+    fn seeds(&self) -> u128 {
+        self.count() >> (self.word_count() / 3)
+    }
+
+    /**
+     * Checks if the search ends in a reasonable time, with or without
+     * deriving the seeds of the candidates
+     */
+    fn is_feasible(&self, derive_seeds: bool) -> bool {
+        match derive_seeds {
+            true => self.seeds() <= MAX_SEEDS,
+            false => self.count() <= MAX_CANDIDATES,
+        }
+    }
+}
+
+/**
+ * The words that may stand at each position of a partly known phrase
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordPattern {
+    choices: Vec<Vec<usize>>,
+}
+
+impl WordPattern {
+    /**
+     * Reads a phrase whose unreadable words are marked. Each word
+     * separated by spaces is either :
+     * - a known word
+     * - `?` for any word of the dictionary
+     * - a prefix followed by `*`, such as `ab*`, for the words starting with it
+     * - candidates separated by `|`, such as `cat|car|ca*`
+     */
+    pub fn parse(phrase: &str, wordlist: &dyn Wordlist) -> Result<WordPattern, Error> {
+        let choices: Vec<Vec<usize>> = phrase
+            .split_whitespace()
+            .map(|token| {
+                let mut indexes: Vec<usize> = Vec::new();
+                for candidate in token.to_lowercase().split('|') {
+                    let matching: Vec<usize> = match candidate.strip_suffix('*') {
+                        _ if candidate == "?" => (0..wordlist.len()).collect(),
+                        Some(prefix) => (0..wordlist.len())
+                            .filter(|index| {
+                                wordlist
+                                    .word(*index)
+                                    .is_some_and(|word| word.starts_with(prefix))
+                            })
+                            .collect(),
+                        None => wordlist.index_of(candidate).into_iter().collect(),
+                    };
+                    if matching.is_empty() {
+                        return Err(Error::UnknownWord(candidate.to_string()));
+                    }
+                    indexes.extend(matching);
+                }
+                indexes.sort_unstable();
+                indexes.dedup();
+                Ok(indexes)
+            })
+            .collect::<Result<_, _>>()?;
+
+        if !Mnemonic::VALID_LENGTHS.contains(&choices.len()) {
+            return Err(Error::ChecksumLength(choices.len()));
+        }
+        Ok(WordPattern { choices })
+    }
+
+    /**
+     * Returns the positions, starting at 1, of the words that are not known
+     */
+    pub fn unknown_positions(&self) -> Vec<usize> {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choices)| choices.len() > 1)
+            .map(|(position, _)| position + 1)
+            .collect()
+    }
+}
+
+impl Candidates for WordPattern {
+    fn count(&self) -> u128 {
+        self.choices.iter().fold(1u128, |count, choices| {
+            count.saturating_mul(choices.len() as u128)
+        })
+    }
+
+    /**
+     * The last position changes first
+     */
+    fn candidate(&self, mut number: u128) -> Option<Vec<usize>> {
+        let mut indexes = vec![0; self.choices.len()];
+        for (index, choices) in indexes.iter_mut().zip(&self.choices).rev() {
+            let size = choices.len() as u128;
+            *index = choices[(number % size) as usize];
+            number /= size;
+        }
+        Some(indexes)
+    }
+
+    fn word_count(&self) -> usize {
+        self.choices.len()
+    }
+}
+
+/**
+ * The words of a phrase whose order is unknown, except at some
+ * fixed positions
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scrambled {
+    /// Index of the word at each fixed position, None elsewhere
+    fixed: Vec<Option<usize>>,
+    /// Indexes of the words to place at the other positions, sorted
+    free: Vec<usize>,
+}
+
+impl Scrambled {
+    /**
+     * Takes all the words of a phrase, the words at the given positions,
+     * starting at 1, being in their place
+     */
+    pub fn new(
+        mnemonic: &Mnemonic,
+        wordlist: &dyn Wordlist,
+        positions: &[usize],
+    ) -> Result<Scrambled, Error> {
+        if !Mnemonic::VALID_LENGTHS.contains(&mnemonic.len()) {
+            return Err(Error::ChecksumLength(mnemonic.len()));
+        }
+        let indexes = mnemonic.indexes(wordlist)?;
+        if let Some(position) = positions
+            .iter()
+            .find(|position| **position == 0 || **position > indexes.len())
+        {
+            return Err(Error::InvalidPosition {
+                position: *position,
+                length: indexes.len(),
+            });
+        }
+
+        let mut fixed = vec![None; indexes.len()];
+        let mut free = Vec::new();
+        for (position, index) in indexes.into_iter().enumerate() {
+            match positions.contains(&(position + 1)) {
+                true => fixed[position] = Some(index),
+                false => free.push(index),
+            }
+        }
+        free.sort_unstable();
+        Ok(Scrambled { fixed, free })
+    }
+}
+
+impl Candidates for Scrambled {
+    /**
+     * Counts the permutations of the free words as if they were all
+     * different
+     */
+    fn count(&self) -> u128 {
+        (1..=self.free.len() as u128).fold(1u128, |count, factor| count.saturating_mul(factor))
+    }
+
+    /**
+     * Places the free words in the order of the permutation of a number,
+     * whose Lehmer code is written in factorial base. Permutations
+     * swapping identical words are skipped, so each order is found once.
+     */
+    fn candidate(&self, mut number: u128) -> Option<Vec<usize>> {
+        let mut remaining = self.free.clone();
+        let mut order = Vec::with_capacity(self.free.len());
+        let mut factorial = self.count() / (self.free.len().max(1) as u128);
+        for left in (1..=self.free.len()).rev() {
+            let chosen = (number / factorial) as usize;
+            number %= factorial;
+            factorial /= (left as u128 - 1).max(1);
+            // Identical words are sorted, so only the first of them is chosen
+            if chosen > 0 && remaining[chosen - 1] == remaining[chosen] {
+                return None;
+            }
+            order.push(remaining.remove(chosen));
+        }
+
+        let mut order = order.into_iter();
+        self.fixed
+            .iter()
+            .map(|fixed| fixed.or_else(|| order.next()))
+            .collect()
+    }
+
+    fn word_count(&self) -> usize {
+        self.fixed.len()
+    }
+}
+
+/**
+ * Builds the phrase of word indexes
+ */
+pub fn to_mnemonic(indexes: &[usize], wordlist: &dyn Wordlist) -> Mnemonic {
+    Mnemonic::from_words(
+        indexes
+            .iter()
+            .map(|index| wordlist.word(*index).unwrap_or_default().to_string())
+            .collect(),
+    )
+}
+
+/**
+ * Searches the candidates, from the one numbered `start`, whose checksum
+ * is valid and, if there is an oracle, whose seed protected by a
+ * passphrase matches it. `report` receives the progress and the phrases
+ * found after each batch, and returns whether the search goes on.
+ */
+pub fn recover(
+    candidates: &dyn Candidates,
+    wordlist: &dyn Wordlist,
+    passphrase: &str,
+    oracle: Option<&Oracle>,
+    start: u128,
+    threads: usize,
+    report: &mut dyn FnMut(&Progress, Vec<Mnemonic>) -> bool,
+) {
+    search::run(
+        candidates.count(),
+        start,
+        threads,
+        |number| {
+            let indexes = candidates.candidate(number)?;
+            Mnemonic::entropy_of_indexes(&indexes).ok()?;
+            let mnemonic = to_mnemonic(&indexes, wordlist);
+            match oracle {
+                Some(oracle) => {
+                    let seed = mnemonic.to_seed(wordlist, passphrase).ok()?;
+                    Some(mnemonic).filter(|_| oracle.matches(&seed))
+                }
+                None => Some(mnemonic),
+            }
+        },
+        report,
+    );
+}
+
+/**
+ * Returns the first candidate matching the oracle, see `recover`.
+ * `report` only receives the progress of the batches without a match.
+ */
+pub fn recover_first(
+    candidates: &dyn Candidates,
+    wordlist: &dyn Wordlist,
+    passphrase: &str,
+    oracle: &Oracle,
+    start: u128,
+    threads: usize,
+    report: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<Mnemonic> {
+    let mut found = None;
+    recover(
+        candidates,
+        wordlist,
+        passphrase,
+        Some(oracle),
+        start,
+        threads,
+        &mut |progress, matches| {
+            // The batch of the match is not reported, so that a checkpoint
+            // resumes before it instead of past it
+            found = found.take().or_else(|| matches.into_iter().next());
+            found.is_none() && report(progress)
+        },
+    );
+    found
+}
+
+/**
+ * Searches the passphrase of a phrase among candidates, from the one
+ * numbered `start`, returning the first whose seed matches the oracle.
+ * `report` receives the progress after each batch without a match and
+ * returns whether the search goes on.
+ */
+pub fn recover_passphrase(
+    mnemonic: &Mnemonic,
+    wordlist: &dyn Wordlist,
+    passphrases: &[String],
+    oracle: &Oracle,
+    start: u128,
+    threads: usize,
+    report: &mut dyn FnMut(&Progress) -> bool,
+) -> Option<String> {
+    let mut found = None;
+    search::run(
+        passphrases.len() as u128,
+        start,
+        threads,
+        |number| {
+            let passphrase = &passphrases[number as usize];
+            let seed = mnemonic.to_seed(wordlist, passphrase).ok()?;
+            Some(passphrase.clone()).filter(|_| oracle.matches(&seed))
+        },
+        &mut |progress, matches| {
+            // The batch of the match is not reported, so that a checkpoint
+            // resumes before it instead of past it
+            found = found.take().or_else(|| matches.into_iter().next());
+            found.is_none() && report(progress)
+        },
+    );
+    found
+}
+
+#[test]
+fn test_pattern() {
+    use crate::language::Language;
+
+    let pattern = WordPattern::parse(
+        "abandon ? abandon abo* abandon abandon abandon abandon abandon abandon abandon \
+         about|abou*|zoo",
+        &Language::English,
+    )
+    .unwrap();
+    assert_eq!(vec![2, 4, 12], pattern.unknown_positions());
+    // abo* matches about and above, about|abou*|zoo matches about and zoo
+    assert_eq!(2048 * 2 * 2, pattern.count());
+    let last = pattern.candidate(pattern.count() - 1).unwrap();
+    assert_eq!(
+        "abandon zoo abandon above abandon abandon abandon abandon abandon abandon abandon zoo",
+        to_mnemonic(&last, &Language::English).to_phrase(&Language::English)
+    );
+    assert_eq!(
+        vec![0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 3],
+        pattern.candidate(0).unwrap()
+    );
+
+    assert_eq!(
+        Err(Error::UnknownWord("abz*".to_string())),
+        WordPattern::parse("abz* ? ? ? ? ? ? ? ? ? ? ?", &Language::English)
+    );
+    assert_eq!(
+        Err(Error::ChecksumLength(11)),
+        WordPattern::parse("? ? ? ? ? ? ? ? ? ? ?", &Language::English)
+    );
+}
+
+#[test]
+fn test_recover() {
+    use crate::language::Language;
+    use crate::oracle::Target;
+
+    let pattern = WordPattern::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         ab* abo*",
+        &Language::English,
+    )
+    .unwrap();
+    let target = Target::parse("73c5da0a").unwrap();
+    let oracle = Oracle::new(target, None, 1).unwrap();
+    let mut reports = 0;
+    let found = recover_first(&pattern, &Language::English, "", &oracle, 0, 2, &mut |_| {
+        reports += 1;
+        true
+    });
+    // The match is in the first batch, which is not reported
+    assert_eq!(0, reports);
+    assert_eq!(
+        Some(Mnemonic::parse(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon about"
+        )),
+        found
+    );
+
+    // The match is the first candidate, so a resumed search misses it
+    assert_eq!(
+        None,
+        recover_first(&pattern, &Language::English, "", &oracle, 1, 2, &mut |_| {
+            true
+        })
+    );
+}
+
+#[test]
+fn test_scrambled() {
+    use crate::language::Language;
+
+    let mnemonic = Mnemonic::parse("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo");
+    let scrambled = Scrambled::new(&mnemonic, &Language::English, &[]).unwrap();
+    assert_eq!(479_001_600, scrambled.count());
+    assert_eq!(Some(vec![2047; 12]), scrambled.candidate(0));
+    assert_eq!(None, scrambled.candidate(1));
+
+    // Each order of the words is found once
+    let mnemonic = Mnemonic::parse(
+        "abandon ability able about abandon abandon abandon abandon abandon abandon abandon abandon",
+    );
+    let scrambled = Scrambled::new(&mnemonic, &Language::English, &[5, 6, 7, 8, 9, 10]).unwrap();
+    assert_eq!(720, scrambled.count());
+    let mut orders: Vec<Vec<usize>> = (0..scrambled.count())
+        .filter_map(|number| scrambled.candidate(number))
+        .collect();
+    assert_eq!(vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 2, 3], orders[0]);
+    assert_eq!(
+        vec![3, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        orders[orders.len() - 1]
+    );
+    orders.sort();
+    orders.dedup();
+    // 6 positions holding 3 different words and the same word 3 times
+    assert_eq!(720 / 6, orders.len());
+    assert!(scrambled.is_feasible(true));
+
+    assert_eq!(
+        Err(Error::InvalidPosition {
+            position: 13,
+            length: 12
+        }),
+        Scrambled::new(&mnemonic, &Language::English, &[13])
+    );
+    let all = Scrambled::new(&mnemonic.clone(), &Language::English, &[]).unwrap();
+    assert!(all.is_feasible(false));
+    let long = Mnemonic::from_words(vec!["abandon".to_string(); 24]);
+    let long = Scrambled::new(&long, &Language::English, &[]).unwrap();
+    assert!(!long.is_feasible(false));
+    assert!(!long.is_feasible(true));
+}
+
+#[test]
+fn test_recover_order() {
+    use crate::language::Language;
+    use crate::oracle::Target;
+
+    let mnemonic = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon about abandon abandon \
+         abandon",
+    );
+    let scrambled = Scrambled::new(&mnemonic, &Language::English, &[1, 2, 3, 4, 5, 6]).unwrap();
+
+    let mut valid = Vec::new();
+    recover(
+        &scrambled,
+        &Language::English,
+        "",
+        None,
+        0,
+        2,
+        &mut |_, matches| {
+            valid.extend(matches);
+            true
+        },
+    );
+    // about is at the last position, the only one of the 6 orders whose checksum is valid
+    let expected = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+    );
+    assert_eq!(vec![expected.clone()], valid);
+
+    let oracle = Oracle::new(Target::parse("73c5da0a").unwrap(), None, 1).unwrap();
+    assert_eq!(
+        Some(expected),
+        recover_first(
+            &scrambled,
+            &Language::English,
+            "",
+            &oracle,
+            0,
+            2,
+            &mut |_| { true }
+        )
+    );
+}
+
+#[test]
+fn test_recover_passphrase() {
+    use crate::bip32::ExtendedKey;
+    use crate::language::Language;
+    use crate::oracle::Target;
+    use crate::typos::Typos;
+
+    let mnemonic = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+    );
+    // Master key of the phrase protected by TREZOR, from the BIP-39 vectors
+    let master = ExtendedKey::from_xprv(
+        "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF",
+    )
+    .unwrap();
+    let target = Target::Fingerprint(master.fingerprint());
+    let oracle = Oracle::new(target, None, 1).unwrap();
+    let passphrases = Typos::default().candidates("trezor\nbitcoin\n");
+
+    let found = recover_passphrase(
+        &mnemonic,
+        &Language::English,
+        &passphrases,
+        &oracle,
+        0,
+        2,
+        &mut |_| true,
+    );
+    assert_eq!(Some("TREZOR".to_string()), found);
+    assert_eq!(
+        None,
+        recover_passphrase(
+            &mnemonic,
+            &Language::English,
+            &Typos::none().candidates("trezor\n"),
+            &oracle,
+            0,
+            2,
+            &mut |_| true,
+        )
+    );
+}
@@ -0,0 +1,177 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+/**
+ * Number of candidates each thread checks in the first batch
+ */
+const FIRST_BATCH: u128 = 16;
+
+/**
+ * Batches are made larger until one lasts at least this long,
+ * so progress is reported about as often
+ */
+const BATCH_DURATION: Duration = Duration::from_millis(500);
+
+/**
+ * Progress of a search over numbered candidates, reported after
+ * each batch
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of the next candidate, all the previous ones are checked
+    pub position: u128,
+    /// Number of candidates of the whole search
+    pub total: u128,
+    /// Number of candidates checked since the search started
+    pub checked: u128,
+    /// Time spent since the search started
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /**
+     * Returns the percentage of the search space checked
+     */
+    pub fn percentage(&self) -> f64 {
+        match self.total {
+            0 => 100.0,
+            total => self.position as f64 * 100.0 / total as f64,
+        }
+    }
+
+    /**
+     * Returns the number of candidates checked per second
+     */
+    pub fn rate(&self) -> f64 {
+        self.checked as f64 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+
+    /**
+     * Estimates the time left to check the remaining candidates
+     */
+    pub fn eta(&self) -> Option<Duration> {
+        if self.checked == 0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.position) as f64;
+        Some(Duration::from_secs_f64(
+            (remaining / self.rate()).min(u64::MAX as f64),
+        ))
+    }
+}
+
+/**
+ * Writes a duration as days, hours, minutes and seconds
+ */
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        3600..=86399 => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+        _ => format!("{}d {:02}h", seconds / 86400, seconds % 86400 / 3600),
+    }
+}
+
+/**
+ * Checks the candidates numbered from `start` to `total` with `test`,
+ * spread over threads. After each batch, `report` receives the
+ * progress and what `test` found in the batch, in candidate order,
+ * and returns whether the search goes on.
+ */
+pub fn run<T, F>(
+    total: u128,
+    start: u128,
+    threads: usize,
+    test: F,
+    report: &mut dyn FnMut(&Progress, Vec<T>) -> bool,
+) where
+    T: Send,
+    F: Fn(u128) -> Option<T> + Sync,
+{
+    let threads = threads.max(1) as u128;
+    let started = Instant::now();
+    let mut position = start.min(total);
+    let mut batch = FIRST_BATCH;
+
+    while position < total {
+        let batch_started = Instant::now();
+        let end = position.saturating_add(batch * threads).min(total);
+        let chunk = (end - position).div_ceil(threads);
+        let test = &test;
+        let found: Vec<T> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|thread| {
+                    let from = (position + thread * chunk).min(end);
+                    let to = (from + chunk).min(end);
+                    scope.spawn(move || (from..to).filter_map(test).collect::<Vec<T>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("search threads don't panic"))
+                .collect()
+        });
+
+        position = end;
+        let progress = Progress {
+            position,
+            total,
+            checked: position - start,
+            elapsed: started.elapsed(),
+        };
+        if !report(&progress, found) {
+            return;
+        }
+        if batch_started.elapsed() < BATCH_DURATION {
+            batch = batch.saturating_mul(2);
+        }
+    }
+}
+
+#[test]
+fn test_run() {
+    let mut found = Vec::new();
+    let mut last = None;
+    run(
+        1000,
+        10,
+        3,
+        |candidate| match candidate % 97 {
+            0 => Some(candidate),
+            _ => None,
+        },
+        &mut |progress, batch| {
+            found.extend(batch);
+            last = Some(*progress);
+            true
+        },
+    );
+    assert_eq!(vec![97, 194, 291, 388, 485, 582, 679, 776, 873, 970], found);
+    let last = last.unwrap();
+    assert_eq!(1000, last.position);
+    assert_eq!(990, last.checked);
+    assert_eq!(Some(Duration::from_secs(0)), last.eta());
+
+    // Stops at the first batch finding something
+    let mut found = Vec::new();
+    run(
+        u128::MAX,
+        0,
+        2,
+        |candidate| Some(candidate).filter(|candidate| *candidate == 100),
+        &mut |_, batch| {
+            found.extend(batch);
+            found.is_empty()
+        },
+    );
+    assert_eq!(vec![100], found);
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!("42s", format_duration(Duration::from_secs(42)));
+    assert_eq!("2m 05s", format_duration(Duration::from_secs(125)));
+    assert_eq!("3h 20m", format_duration(Duration::from_secs(12_000)));
+    assert_eq!("4d 01h", format_duration(Duration::from_secs(349_200)));
+}
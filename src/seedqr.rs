@@ -0,0 +1,237 @@
+use crate::error::Error;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use crate::wordlist::Wordlist;
+use qrcode::bits::Bits;
+use qrcode::render::{svg, unicode};
+use qrcode::types::QrResult;
+use qrcode::{EcLevel, QrCode, Version};
+
+/**
+ * Payload formats of SeedSigner's SeedQR. The standard one holds the
+ * 4 digits index of each english word, the compact one holds the
+ * entropy bytes, without checksum.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Standard,
+    Compact,
+}
+
+/**
+ * Number of pixels of the smallest side of rendered images
+ */
+const IMAGE_SIZE: u32 = 400;
+
+/**
+ * Returns the standard payload of a phrase : the index of each
+ * english word, written with 4 digits
+ */
+pub fn standard_payload(mnemonic: &Mnemonic) -> Result<String, Error> {
+    mnemonic.verify_checksum(&Language::English)?;
+    Ok(mnemonic
+        .indexes(&Language::English)?
+        .iter()
+        .map(|index| format!("{:04}", index))
+        .collect())
+}
+
+/**
+ * Returns the compact payload of a phrase : its entropy
+ */
+pub fn compact_payload(mnemonic: &Mnemonic) -> Result<Vec<u8>, Error> {
+    mnemonic.to_entropy(&Language::English)
+}
+
+/**
+ * Decodes a standard payload into the english phrase,
+ * whose checksum must be valid
+ */
+pub fn decode_standard(payload: &str) -> Result<Mnemonic, Error> {
+    if !payload.bytes().all(|byte| byte.is_ascii_digit()) || !payload.len().is_multiple_of(4) {
+        return Err(Error::InvalidSeedQr(
+            "a standard payload is made of 4 digits groups".to_string(),
+        ));
+    }
+
+    let words = (0..payload.len())
+        .step_by(4)
+        .map(|start| {
+            let index: usize = payload[start..start + 4].parse().unwrap_or(usize::MAX);
+            Language::English
+                .word(index)
+                .map(|word| word.to_string())
+                .ok_or_else(|| {
+                    Error::InvalidSeedQr(format!("word index {} is out of range", index))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mnemonic = Mnemonic::from_words(words);
+    mnemonic.verify_checksum(&Language::English)?;
+    Ok(mnemonic)
+}
+
+/**
+ * Decodes a compact payload into the english phrase of its entropy
+ */
+pub fn decode_compact(payload: &[u8]) -> Result<Mnemonic, Error> {
+    Mnemonic::from_entropy(payload, &Language::English)
+}
+
+/**
+ * Decodes a payload of either format. Standard payloads are told apart
+ * by their digits and their length, a multiple of 4 digits per word.
+ */
+pub fn decode_payload(payload: &[u8]) -> Result<Mnemonic, Error> {
+    let is_standard = payload.iter().all(|byte| byte.is_ascii_digit())
+        && payload.len().is_multiple_of(4)
+        && Mnemonic::VALID_LENGTHS.contains(&(payload.len() / 4));
+    match is_standard {
+        true => decode_standard(&String::from_utf8_lossy(payload)),
+        false => decode_compact(payload),
+    }
+}
+
+/**
+ * Builds the smallest QR code holding the data, with the lowest error
+ * correction level, as SeedSigner does : standard payloads are encoded
+ * in numeric mode, compact ones in byte mode.
+ */
+fn smallest_code(push: impl Fn(&mut Bits) -> QrResult<()>) -> Result<QrCode, Error> {
+    for version in 1..=40 {
+        let mut bits = Bits::new(Version::Normal(version));
+        if push(&mut bits).is_ok() && bits.push_terminator(EcLevel::L).is_ok() {
+            return QrCode::with_bits(bits, EcLevel::L)
+                .map_err(|error| Error::InvalidSeedQr(error.to_string()));
+        }
+    }
+    Err(Error::InvalidSeedQr(
+        "payload is too long for a QR code".to_string(),
+    ))
+}
+
+/**
+ * Returns the SeedQR of a phrase
+ */
+pub fn qr_code(mnemonic: &Mnemonic, format: Format) -> Result<QrCode, Error> {
+    match format {
+        Format::Standard => {
+            let payload = standard_payload(mnemonic)?;
+            smallest_code(|bits| bits.push_numeric_data(payload.as_bytes()))
+        }
+        Format::Compact => {
+            let payload = compact_payload(mnemonic)?;
+            smallest_code(|bits| bits.push_byte_data(&payload))
+        }
+    }
+}
+
+/**
+ * Renders a QR code with unicode blocks, two modules per character.
+ * Colors are inverted so the code can be scanned on dark terminals.
+ */
+pub fn render_terminal(code: &QrCode) -> String {
+    code.render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build()
+}
+
+/**
+ * Renders a QR code as an SVG document
+ */
+pub fn render_svg(code: &QrCode) -> String {
+    code.render::<svg::Color>()
+        .min_dimensions(IMAGE_SIZE, IMAGE_SIZE)
+        .build()
+}
+
+/**
+ * Writes a QR code as a PNG image
+ */
+pub fn save_png(code: &QrCode, path: &str) -> Result<(), Error> {
+    code.render::<image::Luma<u8>>()
+        .min_dimensions(IMAGE_SIZE, IMAGE_SIZE)
+        .build()
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|_| Error::FileWrite(path.to_string()))
+}
+
+/**
+ * Reads the raw payload of the first QR code found in an image file
+ */
+pub fn read_image(path: &str) -> Result<Vec<u8>, Error> {
+    let image = image::open(path)
+        .map_err(|_| Error::ImageLoad(path.to_string()))?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    for grid in prepared.detect_grids() {
+        let mut payload = Vec::new();
+        if grid.decode_to(&mut payload).is_ok() {
+            return Ok(payload);
+        }
+    }
+    Err(Error::QrCodeNotFound(path.to_string()))
+}
+
+#[cfg(test)]
+const TEST_PHRASE: &str = "attack pizza motion avocado network gather crop fresh patrol unusual \
+                           wild holiday candy pony ranch winter theme error hybrid van cereal \
+                           salon goddess expire";
+
+#[test]
+fn test_standard_payload() {
+    // Example of the SeedQR specification
+    let payload = "011513251154012711900771041507421289190620080870026613431420201617920614\
+                   089619290300152408010643";
+    let mnemonic = Mnemonic::parse(TEST_PHRASE);
+    assert_eq!(Ok(payload.to_string()), standard_payload(&mnemonic));
+    assert_eq!(Ok(mnemonic.clone()), decode_standard(payload));
+    assert_eq!(Ok(mnemonic), decode_payload(payload.as_bytes()));
+
+    assert_eq!(
+        Err(Error::InvalidChecksum),
+        decode_standard(&payload.replace("0643", "0644"))
+    );
+    assert!(decode_standard(&payload.replace("0643", "2048")).is_err());
+}
+
+#[test]
+fn test_compact_payload() {
+    use crate::encoding::to_hex;
+
+    let mnemonic = Mnemonic::parse(TEST_PHRASE);
+    let payload = compact_payload(&mnemonic).unwrap();
+    assert_eq!(
+        "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+        to_hex(&payload)
+    );
+    assert_eq!(Ok(mnemonic), decode_payload(&payload));
+}
+
+#[test]
+fn test_qr_code() {
+    let twelve = Mnemonic::parse("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+    let mnemonic = Mnemonic::parse(TEST_PHRASE);
+    // Versions used by SeedSigner
+    let version = |mnemonic: &Mnemonic, format| qr_code(mnemonic, format).unwrap().version();
+    assert_eq!(Version::Normal(2), version(&twelve, Format::Standard));
+    assert_eq!(Version::Normal(3), version(&mnemonic, Format::Standard));
+    assert_eq!(Version::Normal(1), version(&twelve, Format::Compact));
+    assert_eq!(Version::Normal(2), version(&mnemonic, Format::Compact));
+}
+
+#[test]
+fn test_png_round_trip() {
+    let mnemonic = Mnemonic::parse(TEST_PHRASE);
+    let path = std::env::temp_dir().join("b39wc_test_seedqr.png");
+    let path = path.to_str().unwrap();
+
+    for &format in [Format::Standard, Format::Compact].iter() {
+        save_png(&qr_code(&mnemonic, format).unwrap(), path).unwrap();
+        let payload = read_image(path).unwrap();
+        assert_eq!(Ok(mnemonic.clone()), decode_payload(&payload));
+    }
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,130 @@
+use crate::error::Error;
+use crate::mnemonic::Mnemonic;
+use crate::wordlist::Wordlist;
+use rand::RngCore;
+
+/**
+ * Smallest number of parts a phrase can be split into
+ */
+pub const MIN_PARTS: usize = 2;
+
+fn xor(target: &mut [u8], other: &[u8]) {
+    for (byte, other) in target.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+}
+
+/**
+ * Splits a phrase into parts whose entropies XOR together to its
+ * entropy, as Coldcard's Seed XOR does. All the parts but the last
+ * are random, and each of them is a valid phrase of the same length.
+ */
+pub fn split<R: RngCore>(
+    mnemonic: &Mnemonic,
+    wordlist: &dyn Wordlist,
+    parts: usize,
+    rng: &mut R,
+) -> Result<Vec<Mnemonic>, Error> {
+    if parts < MIN_PARTS {
+        return Err(Error::SeedXorParts(parts));
+    }
+    let entropy = mnemonic.to_entropy(wordlist)?;
+
+    let mut last = entropy.clone();
+    let mut entropies: Vec<Vec<u8>> = Vec::with_capacity(parts);
+    for _ in 1..parts {
+        let mut random = vec![0u8; entropy.len()];
+        rng.fill_bytes(&mut random);
+        xor(&mut last, &random);
+        entropies.push(random);
+    }
+    entropies.push(last);
+
+    entropies
+        .iter()
+        .map(|entropy| Mnemonic::from_entropy(entropy, wordlist))
+        .collect()
+}
+
+/**
+ * Combines the parts of a Seed XOR into the original phrase.
+ * Every part must have a valid checksum and the same length.
+ */
+pub fn combine(parts: &[Mnemonic], wordlist: &dyn Wordlist) -> Result<Mnemonic, Error> {
+    if parts.len() < MIN_PARTS {
+        return Err(Error::SeedXorParts(parts.len()));
+    }
+    if parts.iter().any(|part| part.len() != parts[0].len()) {
+        return Err(Error::ShareMismatch("lengths".to_string()));
+    }
+
+    let mut entropy = parts[0].to_entropy(wordlist)?;
+    for part in &parts[1..] {
+        xor(&mut entropy, &part.to_entropy(wordlist)?);
+    }
+    Mnemonic::from_entropy(&entropy, wordlist)
+}
+
+#[test]
+fn test_combine() {
+    use crate::language::Language;
+
+    // Example of the Coldcard documentation
+    let parts: Vec<Mnemonic> = [
+        "romance wink lottery autumn shop bring dawn tongue range crater truth ability miss \
+         spice fitness easy legal release recall obey exchange recycle dragon room",
+        "lion misery divide hurry latin fluid camp advance illegal lab pyramid unaware eager \
+         fringe sick camera series noodle toy crowd jeans select depth lounge",
+        "vault nominee cradle silk own frown throw leg cactus recall talent worry gadget \
+         surface shy planet purpose coffee drip few seven term squeeze educate",
+    ]
+    .iter()
+    .map(|phrase| Mnemonic::parse(phrase))
+    .collect();
+    let expected = Mnemonic::parse(
+        "silent toe meat possible chair blossom wait occur this worth option bag nurse find \
+         fish scene bench asthma bike wage world quit primary indoor",
+    );
+    assert_eq!(Ok(expected), combine(&parts, &Language::English));
+    assert_eq!(
+        Err(Error::SeedXorParts(1)),
+        combine(&parts[..1], &Language::English)
+    );
+}
+
+#[test]
+fn test_split() {
+    use crate::language::Language;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(39);
+    for phrase in [
+        "erupt quit sphere taxi air decade vote mixed life elevator mammal search empower \
+         rabbit barely indoor crush grid slide correct scatter deal tenant verb",
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+    ]
+    .iter()
+    {
+        let mnemonic = Mnemonic::parse(phrase);
+        for &count in [2, 3, 5].iter() {
+            let parts = split(&mnemonic, &Language::English, count, &mut rng).unwrap();
+            assert_eq!(count, parts.len());
+            for part in &parts {
+                assert_eq!(mnemonic.len(), part.len());
+                assert!(part.verify_checksum(&Language::English).is_ok());
+            }
+            assert_eq!(Ok(mnemonic.clone()), combine(&parts, &Language::English));
+        }
+    }
+
+    let invalid = Mnemonic::parse(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon abandon",
+    );
+    assert_eq!(
+        Err(Error::InvalidChecksum),
+        split(&invalid, &Language::English, 2, &mut rng)
+    );
+}
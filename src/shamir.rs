@@ -0,0 +1,222 @@
+use crate::error::Error;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::sync::OnceLock;
+
+/**
+ * Index of the share holding the secret
+ */
+pub const SECRET_INDEX: u8 = 255;
+
+/**
+ * Index of the share holding the digest of the secret
+ */
+pub const DIGEST_INDEX: u8 = 254;
+
+/**
+ * Number of bytes of the digest checking the recovered secret
+ */
+pub const DIGEST_LENGTH: usize = 4;
+
+/**
+ * Exponent and logarithm tables of GF(256) with the Rijndael
+ * polynomial x^8 + x^4 + x^3 + x + 1, built on first use
+ */
+fn tables() -> &'static ([u8; 255], [u8; 256]) {
+    static TABLES: OnceLock<([u8; 255], [u8; 256])> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut value: u16 = 1;
+        for (power, entry) in exp.iter_mut().enumerate() {
+            *entry = value as u8;
+            log[value as usize] = power as u8;
+            // Multiplies by the generator x + 1
+            value ^= value << 1;
+            if value & 0x100 != 0 {
+                value ^= 0x11B;
+            }
+        }
+        (exp, log)
+    })
+}
+
+fn multiply(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = tables();
+    exp[(log[a as usize] as usize + log[b as usize] as usize) % 255]
+}
+
+fn divide(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+    let (exp, log) = tables();
+    exp[(log[a as usize] as usize + 255 - log[b as usize] as usize) % 255]
+}
+
+/**
+ * Computes the value at x of the polynomials going through the shares.
+ * Shares are given with their index and must have distinct indexes
+ * and the same length.
+ */
+pub fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return value.clone();
+    }
+
+    let length = shares.first().map(|(_, value)| value.len()).unwrap_or(0);
+    let mut result = vec![0u8; length];
+    for (i, (xi, value)) in shares.iter().enumerate() {
+        // Lagrange basis polynomial of the share evaluated at x
+        let basis = shares
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1u8, |basis, (_, (xj, _))| {
+                multiply(basis, divide(x ^ xj, xi ^ xj))
+            });
+        for (byte, share_byte) in result.iter_mut().zip(value) {
+            *byte ^= multiply(basis, *share_byte);
+        }
+    }
+    result
+}
+
+/**
+ * Computes the digest of a secret, keyed with random bytes
+ */
+pub fn digest(random: &[u8], secret: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).expect("any key length is valid");
+    mac.update(secret);
+    mac.finalize().into_bytes()[..DIGEST_LENGTH].to_vec()
+}
+
+/**
+ * Splits a secret into `count` shares indexed from 0, any `threshold`
+ * of them recovering it. The first shares are random and the others
+ * are interpolated from them, the digest share and the secret.
+ * The threshold must be between 1 and the count.
+ */
+pub fn split_secret<R: RngCore>(
+    threshold: usize,
+    count: usize,
+    secret: &[u8],
+    rng: &mut R,
+) -> Vec<Vec<u8>> {
+    if threshold == 1 {
+        return vec![secret.to_vec(); count];
+    }
+
+    let mut points: Vec<(u8, Vec<u8>)> = (0..threshold - 2)
+        .map(|index| {
+            let mut value = vec![0u8; secret.len()];
+            rng.fill_bytes(&mut value);
+            (index as u8, value)
+        })
+        .collect();
+    let mut random = vec![0u8; secret.len() - DIGEST_LENGTH];
+    rng.fill_bytes(&mut random);
+    let mut digest_share = digest(&random, secret);
+    digest_share.extend_from_slice(&random);
+    points.push((DIGEST_INDEX, digest_share));
+    points.push((SECRET_INDEX, secret.to_vec()));
+
+    let mut shares: Vec<Vec<u8>> = points[..threshold - 2]
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect();
+    for index in threshold - 2..count {
+        shares.push(interpolate(&points, index as u8));
+    }
+    shares
+}
+
+/**
+ * Recovers a secret from threshold shares.
+ * Unless the threshold is 1, the digest share is computed too
+ * and must match the digest of the secret.
+ */
+pub fn recover_secret(threshold: usize, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, Error> {
+    if shares.len() < threshold || shares.is_empty() {
+        return Err(Error::NotEnoughShares {
+            found: shares.len(),
+            expected: threshold,
+        });
+    }
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let shares = &shares[..threshold];
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (expected, random) = digest_share.split_at(DIGEST_LENGTH);
+    if digest(random, &secret) != expected {
+        return Err(Error::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+/**
+ * Checks that shares agree on a parameter
+ */
+pub fn check_same<S, T: PartialEq>(
+    shares: impl IntoIterator<Item = S>,
+    parameter: &str,
+    get: impl Fn(S) -> T,
+) -> Result<(), Error> {
+    let mut values = shares.into_iter().map(get);
+    let first = values.next();
+    if values.any(|value| Some(value) != first) {
+        return Err(Error::ShareMismatch(parameter.to_string()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_field() {
+    for a in 1..=255u8 {
+        assert_eq!(a, divide(multiply(a, 7), 7));
+        assert_eq!(1, divide(a, a));
+    }
+    // Known product of the Rijndael field
+    assert_eq!(0xC1, multiply(0x57, 0x83));
+}
+
+#[test]
+fn test_interpolate() {
+    // Shares of the line y = 3x + 5 over GF(256)
+    let shares: Vec<(u8, Vec<u8>)> = [1u8, 2]
+        .iter()
+        .map(|&x| (x, vec![multiply(3, x) ^ 5]))
+        .collect();
+    assert_eq!(vec![5], interpolate(&shares, 0));
+    assert_eq!(vec![multiply(3, 9) ^ 5], interpolate(&shares, 9));
+    assert_eq!(vec![multiply(3, 2) ^ 5], interpolate(&shares, 2));
+}
+
+#[test]
+fn test_split_secret() {
+    let secret: Vec<u8> = (0..16).collect();
+    let mut rng = rand::rngs::mock::StepRng::new(1, 3);
+    let shares = split_secret(3, 5, &secret, &mut rng);
+    assert_eq!(5, shares.len());
+
+    let chosen: Vec<(u8, Vec<u8>)> = [4u8, 1, 2]
+        .iter()
+        .map(|&index| (index, shares[index as usize].clone()))
+        .collect();
+    assert_eq!(Ok(secret.clone()), recover_secret(3, &chosen));
+    assert_eq!(
+        vec![secret.clone(); 2],
+        split_secret(1, 2, &secret, &mut rng)
+    );
+
+    let mut corrupted = chosen.clone();
+    corrupted[0].1[0] ^= 1;
+    assert_eq!(Err(Error::InvalidDigest), recover_secret(3, &corrupted));
+}
@@ -0,0 +1,396 @@
+use crate::dictionaries::langs::SLIP39;
+use crate::error::Error;
+use crate::language::Language;
+use crate::shamir::{check_same, recover_secret};
+use crate::wordlist::Wordlist;
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+
+/**
+ * Number of bits encoded by each word
+ */
+const RADIX_BITS: usize = 10;
+
+/**
+ * Number of words holding the identifier and parameters of a share
+ */
+const METADATA_WORDS: usize = 4;
+
+/**
+ * Number of words holding the checksum of a share
+ */
+const CHECKSUM_WORDS: usize = 3;
+
+/**
+ * Smallest number of bytes of a secret
+ */
+const MIN_SECRET_LENGTH: usize = 16;
+
+/**
+ * Generator of the RS1024 checksum
+ */
+const GENERATOR: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+    0x21B1F890, 0x3F3F120,
+];
+
+/**
+ * Number of PBKDF2 iterations of the encryption, shifted left
+ * by the iteration exponent of the share
+ */
+const BASE_ITERATION_COUNT: u32 = 10000;
+
+/**
+ * Number of rounds of the Feistel network encrypting the secret
+ */
+const ROUND_COUNT: u8 = 4;
+
+/**
+ * The dictionary of SLIP-39 shares, 1024 words encoding 10 bits each
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slip39Wordlist;
+
+impl Slip39Wordlist {
+    pub const SIZE: usize = 1024;
+}
+
+impl Wordlist for Slip39Wordlist {
+    fn name(&self) -> String {
+        "slip39".to_string()
+    }
+
+    fn language(&self) -> Option<Language> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        SLIP39.len()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        SLIP39.get(index).copied()
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        indexes().get(word).copied()
+    }
+}
+
+/**
+ * Returns the index of every word of the SLIP-39 dictionary,
+ * built on first use
+ */
+fn indexes() -> &'static HashMap<&'static str, usize> {
+    static INDEXES: OnceLock<HashMap<&'static str, usize>> = OnceLock::new();
+    INDEXES.get_or_init(|| {
+        SLIP39
+            .iter()
+            .enumerate()
+            .map(|(index, &word)| (word, index))
+            .collect()
+    })
+}
+
+fn polymod(values: impl Iterator<Item = u32>) -> u32 {
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 20;
+        checksum = ((checksum & 0xFFFFF) << 10) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+/**
+ * A SLIP-39 share, holding a part of an encrypted master secret.
+ * Indexes are stored as encoded, starting at 0, thresholds and counts
+ * as their actual values.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub identifier: u16,
+    pub extendable: bool,
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    /**
+     * Decodes a share from its words, checking its checksum and padding
+     */
+    pub fn from_words(words: &[String]) -> Result<Share, Error> {
+        let indexes: Vec<u32> = words
+            .iter()
+            .map(|word| {
+                Slip39Wordlist
+                    .index_of(word)
+                    .map(|index| index as u32)
+                    .ok_or_else(|| Error::UnknownWord(word.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let value_words = words.len().saturating_sub(METADATA_WORDS + CHECKSUM_WORDS);
+        let padding = (RADIX_BITS * value_words) % 16;
+        if padding > 8 || value_words * RADIX_BITS < MIN_SECRET_LENGTH * 8 {
+            return Err(Error::ShareLength(words.len()));
+        }
+
+        let extendable = (indexes[1] >> 4) & 1 == 1;
+        let values = customization(extendable)
+            .iter()
+            .map(|&byte| byte as u32)
+            .chain(indexes.iter().copied());
+        if polymod(values) != 1 {
+            return Err(Error::InvalidShareChecksum);
+        }
+
+        let identification = (indexes[0] << 10) | indexes[1];
+        let parameters = (indexes[2] << 10) | indexes[3];
+        let group_threshold = ((parameters >> 12) & 0xF) as u8 + 1;
+        let group_count = ((parameters >> 8) & 0xF) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(Error::GroupThreshold {
+                threshold: group_threshold as usize,
+                count: group_count as usize,
+            });
+        }
+
+        let bits: Vec<bool> = indexes[METADATA_WORDS..indexes.len() - CHECKSUM_WORDS]
+            .iter()
+            .flat_map(|index| {
+                (0..RADIX_BITS)
+                    .rev()
+                    .map(move |shift| (index >> shift) & 1 == 1)
+            })
+            .collect();
+        if bits[..padding].iter().any(|&bit| bit) {
+            return Err(Error::SharePadding);
+        }
+        let value = bits[padding..]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+            .collect();
+
+        Ok(Share {
+            identifier: (identification >> 5) as u16,
+            extendable,
+            iteration_exponent: (identification & 0xF) as u8,
+            group_index: (parameters >> 16) as u8,
+            group_threshold,
+            group_count,
+            member_index: ((parameters >> 4) & 0xF) as u8,
+            member_threshold: (parameters & 0xF) as u8 + 1,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "identifier {}, group {} of {} ({} needed), member {} ({} needed)",
+            self.identifier,
+            self.group_index + 1,
+            self.group_count,
+            self.group_threshold,
+            self.member_index + 1,
+            self.member_threshold
+        )
+    }
+}
+
+/**
+ * Decrypts the master secret with the Feistel network of SLIP-39
+ */
+fn decrypt(encrypted: &[u8], passphrase: &[u8], share: &Share) -> Vec<u8> {
+    let half = encrypted.len() / 2;
+    let mut left = encrypted[..half].to_vec();
+    let mut right = encrypted[half..].to_vec();
+
+    let mut salt: Vec<u8> = Vec::new();
+    if !share.extendable {
+        salt.extend_from_slice(b"shamir");
+        salt.extend_from_slice(&share.identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << share.iteration_exponent) / ROUND_COUNT as u32;
+
+    for round in (0..ROUND_COUNT).rev() {
+        let mut password = vec![round];
+        password.extend_from_slice(passphrase);
+        let mut key = vec![0u8; right.len()];
+        pbkdf2_hmac::<Sha256>(
+            &password,
+            &[&salt[..], &right[..]].concat(),
+            iterations,
+            &mut key,
+        );
+
+        let next: Vec<u8> = left.iter().zip(&key).map(|(a, b)| a ^ b).collect();
+        left = std::mem::replace(&mut right, next);
+    }
+
+    [right, left].concat()
+}
+
+/**
+ * Recovers the master secret from shares.
+ * Shares must share the same identifier and parameters, enough groups
+ * must be complete, and the passphrase is used to decrypt the secret.
+ * Groups or members beyond the thresholds are ignored.
+ */
+pub fn combine(shares: &[Share], passphrase: &[u8]) -> Result<Vec<u8>, Error> {
+    let first = match shares.first() {
+        Some(share) => share,
+        None => {
+            return Err(Error::NotEnoughShares {
+                found: 0,
+                expected: 1,
+            })
+        }
+    };
+    check_same(shares, "identifiers", |share| share.identifier)?;
+    check_same(shares, "extendable flags", |share| share.extendable)?;
+    check_same(shares, "iteration exponents", |share| {
+        share.iteration_exponent
+    })?;
+    check_same(shares, "group thresholds", |share| share.group_threshold)?;
+    check_same(shares, "group counts", |share| share.group_count)?;
+    check_same(shares, "lengths", |share| share.value.len())?;
+
+    let mut groups: BTreeMap<u8, BTreeMap<u8, &Share>> = BTreeMap::new();
+    for share in shares {
+        let members = groups.entry(share.group_index).or_default();
+        if let Some(other) = members.insert(share.member_index, share) {
+            if other.value != share.value {
+                return Err(Error::DuplicateShare {
+                    group: share.group_index as usize + 1,
+                    member: share.member_index as usize + 1,
+                });
+            }
+        }
+    }
+
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::new();
+    let mut incomplete: Option<Error> = None;
+    for (&group_index, members) in groups.iter() {
+        let members: Vec<&Share> = members.values().copied().collect();
+        check_same(
+            members.iter().copied(),
+            &format!("member thresholds in group {}", group_index + 1),
+            |share| share.member_threshold,
+        )?;
+
+        let threshold = members[0].member_threshold as usize;
+        if members.len() < threshold {
+            incomplete.get_or_insert(Error::NotEnoughMembers {
+                group: group_index as usize + 1,
+                found: members.len(),
+                expected: threshold,
+            });
+            continue;
+        }
+        let values: Vec<(u8, Vec<u8>)> = members
+            .iter()
+            .map(|share| (share.member_index, share.value.clone()))
+            .collect();
+        group_secrets.push((group_index, recover_secret(threshold, &values)?));
+    }
+
+    let threshold = first.group_threshold as usize;
+    if group_secrets.len() < threshold {
+        return Err(incomplete.unwrap_or(Error::NotEnoughGroups {
+            found: group_secrets.len(),
+            expected: threshold,
+        }));
+    }
+
+    let encrypted = recover_secret(threshold, &group_secrets)?;
+    Ok(decrypt(&encrypted, passphrase, first))
+}
+
+#[cfg(test)]
+fn test_shares(phrases: &[&str]) -> Result<Vec<Share>, Error> {
+    phrases
+        .iter()
+        .map(|phrase| {
+            let words: Vec<String> = phrase.split(' ').map(String::from).collect();
+            Share::from_words(&words)
+        })
+        .collect()
+}
+
+#[test]
+fn test_share() {
+    let shares = test_shares(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"]).unwrap();
+    assert_eq!(1, shares[0].group_threshold);
+    assert_eq!(1, shares[0].member_threshold);
+    assert_eq!(16, shares[0].value.len());
+    assert_eq!(
+        "identifier 7945, group 1 of 1 (1 needed), member 1 (1 needed)",
+        shares[0].to_string()
+    );
+
+    assert_eq!(
+        Err(Error::InvalidShareChecksum),
+        test_shares(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"])
+    );
+    assert_eq!(
+        Err(Error::SharePadding),
+        test_shares(&["duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"])
+    );
+    assert_eq!(
+        Err(Error::ShareLength(19)),
+        test_shares(&["junk necklace academic academic acne isolate join hesitate lunar roster dough calcium chemical ladybug amount mobile glasses verify cylinder"])
+    );
+}
+
+#[test]
+fn test_combine() {
+    use crate::encoding::to_hex;
+
+    let shares = test_shares(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"]).unwrap();
+    assert_eq!(
+        "bb54aac4b89dc868ba37d9cc21b2cece",
+        to_hex(&combine(&shares, b"TREZOR").unwrap())
+    );
+
+    let shares = test_shares(&[
+        "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+        "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+    ])
+    .unwrap();
+    assert_eq!(
+        "b43ceb7e57a0ea8766221624d01b0864",
+        to_hex(&combine(&shares, b"TREZOR").unwrap())
+    );
+    assert_eq!(
+        Err(Error::NotEnoughMembers {
+            group: 1,
+            found: 1,
+            expected: 2
+        }),
+        combine(&shares[..1], b"TREZOR")
+    );
+}
@@ -0,0 +1,427 @@
+use crate::bytewords::{self, Style};
+use crate::error::Error;
+use crate::shamir::{check_same, recover_secret, split_secret};
+use crate::ur::Cbor;
+use rand::RngCore;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/**
+ * Number of bytes holding the identifier and parameters of a share
+ */
+const METADATA_LENGTH: usize = 5;
+
+/**
+ * CBOR tag of an SSKR share
+ */
+const TAG: u64 = 309;
+
+/**
+ * Largest number of groups, and of members in a group
+ */
+pub const MAX_SHARES: usize = 16;
+
+/**
+ * Threshold and count of members of an SSKR group, written `2-of-3`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSpec {
+    pub threshold: usize,
+    pub count: usize,
+}
+
+impl FromStr for GroupSpec {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<GroupSpec, Error> {
+        let numbers: Vec<usize> = spec
+            .split("-of-")
+            .map(|number| number.trim().parse().ok())
+            .collect::<Option<Vec<usize>>>()
+            .unwrap_or_default();
+        match numbers[..] {
+            [threshold, count] if 1 <= threshold && threshold <= count && count <= MAX_SHARES => {
+                Ok(GroupSpec { threshold, count })
+            }
+            _ => Err(Error::InvalidSskrGroup(spec.to_string())),
+        }
+    }
+}
+
+/**
+ * An SSKR share of Blockchain Commons, holding a part of a secret.
+ * Indexes are stored as encoded, starting at 0, thresholds and counts
+ * as their actual values.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub identifier: u16,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    /**
+     * Decodes a share from its metadata and value bytes
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Share, Error> {
+        let invalid = |reason: &str| Error::InvalidSskrShare(reason.to_string());
+        let value_length = bytes.len().saturating_sub(METADATA_LENGTH);
+        if !(16..=32).contains(&value_length) || !value_length.is_multiple_of(2) {
+            return Err(invalid(&format!(
+                "a value of {} bytes can't hold a secret",
+                value_length
+            )));
+        }
+        if bytes[4] >> 4 != 0 {
+            return Err(invalid("reserved bits are not zero"));
+        }
+
+        let share = Share {
+            identifier: u16::from_be_bytes([bytes[0], bytes[1]]),
+            group_threshold: (bytes[2] >> 4) + 1,
+            group_count: (bytes[2] & 0xF) + 1,
+            group_index: bytes[3] >> 4,
+            member_threshold: (bytes[3] & 0xF) + 1,
+            member_index: bytes[4] & 0xF,
+            value: bytes[METADATA_LENGTH..].to_vec(),
+        };
+        if share.group_threshold > share.group_count {
+            return Err(Error::GroupThreshold {
+                threshold: share.group_threshold as usize,
+                count: share.group_count as usize,
+            });
+        }
+        if share.group_index >= share.group_count {
+            return Err(invalid("group index is greater than group count"));
+        }
+        Ok(share)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.identifier.to_be_bytes().to_vec();
+        bytes.push(((self.group_threshold - 1) << 4) | (self.group_count - 1));
+        bytes.push((self.group_index << 4) | (self.member_threshold - 1));
+        bytes.push(self.member_index);
+        bytes.extend_from_slice(&self.value);
+        bytes
+    }
+
+    /**
+     * Decodes a share written as Bytewords, with whole words separated
+     * by spaces or dashes, or minimal. The CBOR byte string they hold
+     * may be tagged as an SSKR share.
+     */
+    pub fn parse(encoded: &str) -> Result<Share, Error> {
+        let encoded = encoded.trim();
+        let style = if encoded.contains(char::is_whitespace) {
+            Style::Standard
+        } else if encoded.contains('-') {
+            Style::Uri
+        } else {
+            Style::Minimal
+        };
+        match Cbor::from_bytes(&bytewords::decode(encoded, style)?) {
+            Ok(Cbor::Tag(TAG, bytes)) => match *bytes {
+                Cbor::Bytes(bytes) => Share::from_bytes(&bytes),
+                _ => Err(Error::InvalidSskrShare("not a byte string".to_string())),
+            },
+            Ok(Cbor::Bytes(bytes)) => Share::from_bytes(&bytes),
+            _ => Err(Error::InvalidSskrShare("not a byte string".to_string())),
+        }
+    }
+
+    /**
+     * Encodes the share as Bytewords of its tagged CBOR, the
+     * `tuna acid epic...` format of Gordian tools
+     */
+    pub fn encode(&self) -> String {
+        let cbor = Cbor::Tag(TAG, Box::new(Cbor::Bytes(self.to_bytes())));
+        bytewords::encode(&cbor.to_bytes(), Style::Standard)
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "identifier {}, group {} of {} ({} needed), member {} ({} needed)",
+            self.identifier,
+            self.group_index + 1,
+            self.group_count,
+            self.group_threshold,
+            self.member_index + 1,
+            self.member_threshold
+        )
+    }
+}
+
+/**
+ * Splits a secret into groups of shares. `group_threshold` groups
+ * are needed to recover it, and each group needs its own threshold
+ * of members.
+ */
+pub fn split<R: RngCore>(
+    secret: &[u8],
+    group_threshold: usize,
+    groups: &[GroupSpec],
+    rng: &mut R,
+) -> Result<Vec<Vec<Share>>, Error> {
+    if group_threshold == 0 || group_threshold > groups.len() || groups.len() > MAX_SHARES {
+        return Err(Error::GroupThreshold {
+            threshold: group_threshold,
+            count: groups.len(),
+        });
+    }
+    if !(16..=32).contains(&secret.len()) || !secret.len().is_multiple_of(2) {
+        return Err(Error::EntropyLength(secret.len() * 8));
+    }
+
+    let mut identifier = [0u8; 2];
+    rng.fill_bytes(&mut identifier);
+    let group_secrets = split_secret(group_threshold, groups.len(), secret, rng);
+
+    Ok(groups
+        .iter()
+        .zip(group_secrets)
+        .enumerate()
+        .map(|(group_index, (group, group_secret))| {
+            split_secret(group.threshold, group.count, &group_secret, rng)
+                .into_iter()
+                .enumerate()
+                .map(|(member_index, value)| Share {
+                    identifier: u16::from_be_bytes(identifier),
+                    group_index: group_index as u8,
+                    group_threshold: group_threshold as u8,
+                    group_count: groups.len() as u8,
+                    member_index: member_index as u8,
+                    member_threshold: group.threshold as u8,
+                    value,
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/**
+ * Recovers the secret from shares of enough groups
+ */
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>, Error> {
+    let first = match shares.first() {
+        Some(share) => share,
+        None => {
+            return Err(Error::NotEnoughShares {
+                found: 0,
+                expected: 1,
+            })
+        }
+    };
+    check_same(shares, "identifiers", |share| share.identifier)?;
+    check_same(shares, "group thresholds", |share| share.group_threshold)?;
+    check_same(shares, "group counts", |share| share.group_count)?;
+    check_same(shares, "lengths", |share| share.value.len())?;
+
+    let mut groups: BTreeMap<u8, BTreeMap<u8, &Share>> = BTreeMap::new();
+    for share in shares {
+        let members = groups.entry(share.group_index).or_default();
+        if let Some(other) = members.insert(share.member_index, share) {
+            if other.value != share.value {
+                return Err(Error::DuplicateShare {
+                    group: share.group_index as usize + 1,
+                    member: share.member_index as usize + 1,
+                });
+            }
+        }
+    }
+
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::new();
+    let mut incomplete: Option<Error> = None;
+    for (&group_index, members) in groups.iter() {
+        let members: Vec<&Share> = members.values().copied().collect();
+        check_same(
+            members.iter().copied(),
+            &format!("member thresholds in group {}", group_index + 1),
+            |share| share.member_threshold,
+        )?;
+
+        let threshold = members[0].member_threshold as usize;
+        if members.len() < threshold {
+            incomplete.get_or_insert(Error::NotEnoughMembers {
+                group: group_index as usize + 1,
+                found: members.len(),
+                expected: threshold,
+            });
+            continue;
+        }
+        let values: Vec<(u8, Vec<u8>)> = members
+            .iter()
+            .map(|share| (share.member_index, share.value.clone()))
+            .collect();
+        group_secrets.push((group_index, recover_secret(threshold, &values)?));
+    }
+
+    let threshold = first.group_threshold as usize;
+    if group_secrets.len() < threshold {
+        return Err(incomplete.unwrap_or(Error::NotEnoughGroups {
+            found: group_secrets.len(),
+            expected: threshold,
+        }));
+    }
+    recover_secret(threshold, &group_secrets)
+}
+
+#[test]
+fn test_group_spec() {
+    assert_eq!(
+        Ok(GroupSpec {
+            threshold: 2,
+            count: 3
+        }),
+        "2-of-3".parse()
+    );
+    for spec in ["3-of-2", "0-of-1", "1-of-17", "2of3", "2-of-"].iter() {
+        assert_eq!(
+            Err(Error::InvalidSskrGroup(spec.to_string())),
+            spec.parse::<GroupSpec>()
+        );
+    }
+}
+
+#[test]
+fn test_share() {
+    let share = Share {
+        identifier: 0x1234,
+        group_index: 1,
+        group_threshold: 2,
+        group_count: 3,
+        member_index: 4,
+        member_threshold: 3,
+        value: vec![0xAB; 16],
+    };
+    let bytes = share.to_bytes();
+    assert_eq!([0x12, 0x34, 0x12, 0x12, 0x04], bytes[..5]);
+    assert_eq!(
+        "identifier 4660, group 2 of 3 (2 needed), member 5 (3 needed)",
+        share.to_string()
+    );
+
+    let encoded = share.encode();
+    assert!(encoded.starts_with("tuna acid epic"));
+    assert_eq!(Ok(share.clone()), Share::parse(&encoded));
+    assert_eq!(
+        Ok(share.clone()),
+        Share::parse(&bytewords::encode(
+            &Cbor::Bytes(bytes.clone()).to_bytes(),
+            Style::Minimal
+        ))
+    );
+
+    let mut reserved = bytes.clone();
+    reserved[4] |= 0x10;
+    assert!(matches!(
+        Share::from_bytes(&reserved),
+        Err(Error::InvalidSskrShare(_))
+    ));
+    assert!(matches!(
+        Share::from_bytes(&bytes[..20]),
+        Err(Error::InvalidSskrShare(_))
+    ));
+}
+
+#[test]
+fn test_split_combine() {
+    let secret: Vec<u8> = (0..32).collect();
+    let groups = [
+        GroupSpec {
+            threshold: 2,
+            count: 3,
+        },
+        GroupSpec {
+            threshold: 1,
+            count: 1,
+        },
+        GroupSpec {
+            threshold: 3,
+            count: 5,
+        },
+    ];
+    let mut rng = rand::rngs::mock::StepRng::new(7, 11);
+    let shares = split(&secret, 2, &groups, &mut rng).unwrap();
+    assert_eq!(
+        vec![3, 1, 5],
+        shares.iter().map(Vec::len).collect::<Vec<_>>()
+    );
+
+    let chosen = vec![
+        shares[0][2].clone(),
+        shares[2][4].clone(),
+        shares[0][0].clone(),
+        shares[2][0].clone(),
+        shares[2][1].clone(),
+    ];
+    assert_eq!(Ok(secret.clone()), combine(&chosen));
+    assert_eq!(
+        Ok(secret.clone()),
+        combine(&[
+            shares[1][0].clone(),
+            shares[0][1].clone(),
+            shares[0][0].clone()
+        ])
+    );
+    assert_eq!(
+        Err(Error::NotEnoughMembers {
+            group: 1,
+            found: 1,
+            expected: 2
+        }),
+        combine(&chosen[1..])
+    );
+    assert_eq!(
+        Err(Error::GroupThreshold {
+            threshold: 4,
+            count: 3
+        }),
+        split(&secret, 4, &groups, &mut rng)
+    );
+}
+
+#[test]
+fn test_bc_sskr_vector() {
+    use crate::encoding::{from_hex, to_hex};
+
+    // Shares of 2 groups of 2-of-3 generated by bc-sskr 0.12.0 with the
+    // fake random number generator of bc-rand
+    let shares: Vec<Share> = [
+        "7eb51101004b2dd0b8e5b7b6ddba860333a9bb57c3c82d238ca095fe07c0fdbffead3b28b6",
+        "7eb5110101703e504b7f9cb4b79daeca431b31b436d6cba32f6b1068000bdfad0e94df344d",
+        "7eb51101023d0bcb45cae1b209f4d68ad3d6b48a32f4fa38d12d84c9094db99b05dfe8105b",
+        "7eb511110015d8034446d18199f27509857e6da630224ddec0d711661fbba3c601899d0d41",
+        "7eb5111101e34ad2e9d6eaa08d6710ee806c66ba23633a068253f38f154e410d72707eb86a",
+        "7eb5111102e2e7ba057da7c3b1c3bfdc8f5a7b9e16a0a37544c4ceaf0b4a7c4be760407c17",
+    ]
+    .iter()
+    .map(|share| Share::from_bytes(&from_hex(share).unwrap()).unwrap())
+    .collect();
+    assert_eq!(
+        "identifier 32437, group 2 of 2 (2 needed), member 3 (2 needed)",
+        shares[5].to_string()
+    );
+
+    let secret = "204188bfa6b440a1bdfd6753ff55a8241e07af5c5be943db917e3efabc184b1a";
+    let chosen = [
+        shares[0].clone(),
+        shares[1].clone(),
+        shares[3].clone(),
+        shares[5].clone(),
+    ];
+    assert_eq!(
+        Ok(secret.to_string()),
+        combine(&chosen).map(|secret| to_hex(&secret))
+    );
+    assert!(combine(&shares[..3]).is_err());
+}
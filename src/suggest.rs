@@ -0,0 +1,107 @@
+use crate::wordlist::Wordlist;
+
+/**
+ * Maximum number of suggestions returned for a word
+ */
+const MAX_SUGGESTIONS: usize = 5;
+
+/**
+ * Maximum edit distance for a dictionary word to be suggested
+ */
+const MAX_DISTANCE: usize = 2;
+
+/**
+ * Computes the edit distance between two words.
+ * Insertions, deletions, substitutions and transpositions
+ * of adjacent letters each count as one edit.
+ */
+pub fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[test]
+fn test_distance() {
+    assert_eq!(0, distance("erupt", "erupt"));
+    assert_eq!(1, distance("erupt", "erup"));
+    assert_eq!(1, distance("erupt", "eropt"));
+    assert_eq!(1, distance("erupt", "reupt"));
+    assert_eq!(2, distance("erupt", "ruept"));
+    assert_eq!(5, distance("", "erupt"));
+}
+
+/**
+ * Returns the words of a dictionary close to a word that could not be found.
+ * Words sharing the first four letters come first, then words
+ * sorted by edit distance.
+ */
+pub fn suggest<'a>(wordlist: &'a dyn Wordlist, word: &str) -> Vec<&'a str> {
+    let prefix: String = word.chars().take(4).collect();
+    let mut candidates: Vec<(usize, &str)> = Vec::new();
+
+    if prefix.chars().count() == 4 {
+        for candidate in wordlist.words_with_prefix(&prefix) {
+            candidates.push((0, candidate));
+        }
+    }
+
+    for candidate in (0..wordlist.len()).filter_map(|index| wordlist.word(index)) {
+        let distance = distance(word, candidate);
+        if distance <= MAX_DISTANCE && !candidates.iter().any(|(_, c)| *c == candidate) {
+            candidates.push((distance, candidate));
+        }
+    }
+
+    candidates.sort_by_key(|(distance, _)| *distance);
+    candidates
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/**
+ * Returns the suggestions for a word from all the provided dictionaries
+ */
+pub fn suggest_all<'a>(wordlists: &'a [Box<dyn Wordlist>], word: &str) -> Vec<&'a str> {
+    let mut suggestions: Vec<&str> = Vec::new();
+    for wordlist in wordlists {
+        for suggestion in suggest(wordlist.as_ref(), word) {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+    }
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+#[test]
+fn test_suggest() {
+    use crate::language::Language;
+
+    assert_eq!("erupt", suggest(&Language::English, "erubt")[0]);
+    assert_eq!("elevator", suggest(&Language::English, "elevatro")[0]);
+    assert_eq!("quit", suggest(&Language::English, "quti")[0]);
+    assert!(suggest(&Language::English, "cswisafraud").is_empty());
+}
@@ -0,0 +1,388 @@
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+
+/**
+ * Invisible characters removed from words, with their Unicode name
+ */
+const INVISIBLE_CHARACTERS: [(char, &str); 24] = [
+    ('\u{00AD}', "SOFT HYPHEN"),
+    ('\u{034F}', "COMBINING GRAPHEME JOINER"),
+    ('\u{061C}', "ARABIC LETTER MARK"),
+    ('\u{115F}', "HANGUL CHOSEONG FILLER"),
+    ('\u{1160}', "HANGUL JUNGSEONG FILLER"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{3164}', "HANGUL FILLER"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+    ('\u{FFA0}', "HALFWIDTH HANGUL FILLER"),
+];
+
+/**
+ * Characters separating words besides Unicode whitespace,
+ * which covers ideographic and no-break spaces, tabs and newlines.
+ * Commas and semicolons separate values of CSV exports.
+ */
+const SEPARATORS: [char; 5] = [',', ';', '\u{3001}', '\u{FF0C}', '\u{FE50}'];
+
+/**
+ * Returns the Unicode name of an invisible character
+ */
+pub fn invisible_name(character: char) -> Option<&'static str> {
+    INVISIBLE_CHARACTERS
+        .iter()
+        .find(|(c, _)| *c == character)
+        .map(|(_, name)| *name)
+}
+
+/**
+ * Checks if a character separates words
+ */
+pub fn is_separator(character: char) -> bool {
+    character.is_whitespace() || SEPARATORS.contains(&character)
+}
+
+/**
+ * An invisible character removed from the phrase.
+ * Position is the index of the character in the phrase, starting at 1.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrippedCharacter {
+    pub character: char,
+    pub position: usize,
+}
+
+impl fmt::Display for StrippedCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "U+{:04X} {} at position {}",
+            self.character as u32,
+            invisible_name(self.character).unwrap_or("INVISIBLE CHARACTER"),
+            self.position
+        )
+    }
+}
+
+/**
+ * How the words of a phrase were laid out
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Words follow each other in reading order
+    Plain,
+    /// Every word has its own number, words were sorted by them
+    Numbered,
+    /// Words are laid out on several lines of the same length,
+    /// they are read row by row unless told otherwise
+    Grid { rows: usize, columns: usize },
+}
+
+/**
+ * The words of a phrase and the invisible characters removed from them
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tokens {
+    pub words: Vec<String>,
+    pub stripped: Vec<StrippedCharacter>,
+    pub layout: Layout,
+}
+
+impl Tokens {
+    /**
+     * Returns the words of a grid read column by column.
+     * Returns None if the words are not laid out as a grid.
+     */
+    pub fn column_major(&self) -> Option<Vec<String>> {
+        match self.layout {
+            Layout::Grid { rows, columns } => Some(
+                (0..columns)
+                    .flat_map(|column| (0..rows).map(move |row| row * columns + column))
+                    .map(|index| self.words[index].clone())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * A piece of the phrase between separators
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    /// A bare number, such as "3" or "4."
+    Number(usize),
+    /// A word, with the number it was prefixed with, as in "2.quit"
+    Word(&'a str, Option<usize>),
+}
+
+/**
+ * Splits a numbered prefix such as "1.", "2)", "#3" or "04:" from a token.
+ * A token made only of a number, with or without its punctuation,
+ * gives a Number token.
+ */
+fn classify(token: &str) -> Token<'_> {
+    let rest = token.strip_prefix('#').unwrap_or(token);
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    let number = match rest[..digits].parse::<usize>() {
+        Ok(number) => number,
+        Err(_) => return Token::Word(token, None),
+    };
+
+    let rest = &rest[digits..];
+    match rest.strip_prefix(|c| c == '.' || c == ')' || c == ':' || c == '-') {
+        Some("") => Token::Number(number),
+        Some(word) => Token::Word(word, Some(number)),
+        None if rest.is_empty() => Token::Number(number),
+        None => Token::Word(token, None),
+    }
+}
+
+/**
+ * Removes double quotes surrounding a CSV value
+ */
+fn unquote(token: &str) -> &str {
+    token
+        .strip_prefix('"')
+        .and_then(|token| token.strip_suffix('"'))
+        .unwrap_or(token)
+}
+
+/**
+ * Pairs words with their numbers, written either before the words
+ * as in "1. erupt" or after them as in "erupt 1".
+ * Returns the words with their line and number.
+ */
+fn number_words<'a>(tokens: &[(usize, Token<'a>)]) -> Vec<(usize, &'a str, Option<usize>)> {
+    let numbers_first = matches!(
+        tokens.first(),
+        Some((_, Token::Number(_))) | Some((_, Token::Word(_, Some(_))))
+    );
+
+    let mut words: Vec<(usize, &str, Option<usize>)> = Vec::new();
+    let mut pending: Option<usize> = None;
+    for (line, token) in tokens {
+        match *token {
+            Token::Number(number) if numbers_first => pending = Some(number),
+            Token::Number(number) => {
+                if let Some(last) = words.last_mut().filter(|last| last.2.is_none()) {
+                    last.2 = Some(number);
+                }
+            }
+            Token::Word(word, number) => {
+                words.push((*line, word, number.or_else(|| pending.take())));
+            }
+        }
+    }
+    words
+}
+
+/**
+ * Splits a phrase into words.
+ * Words may be separated by any Unicode whitespace, including
+ * ideographic spaces used by japanese phrases, by commas or semicolons.
+ * When every word has its own number, written before or after it,
+ * words are sorted by these numbers. Otherwise numbers are dropped and
+ * lines of the same length are recognized as a grid.
+ * Invisible characters are removed from words and reported.
+ * Words are NFKD normalized, as are the words of the dictionaries.
+ */
+pub fn tokenize(phrase: &str) -> Tokens {
+    let mut stripped: Vec<StrippedCharacter> = Vec::new();
+    let mut tokens: Vec<(usize, String)> = Vec::new();
+    let mut current = String::new();
+    let mut line = 0;
+
+    for (index, character) in phrase.chars().enumerate() {
+        if invisible_name(character).is_some() {
+            stripped.push(StrippedCharacter {
+                character,
+                position: index + 1,
+            });
+        } else if is_separator(character) {
+            if !current.is_empty() {
+                tokens.push((line, std::mem::take(&mut current)));
+            }
+            if character == '\n' {
+                line += 1;
+            }
+        } else {
+            current.push(character);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((line, current));
+    }
+
+    let tokens: Vec<(usize, Token)> = tokens
+        .iter()
+        .map(|(line, token)| (*line, unquote(token)))
+        .filter(|(_, token)| !token.is_empty())
+        .map(|(line, token)| (line, classify(token)))
+        .collect();
+    let mut words = number_words(&tokens);
+
+    let mut numbers: Vec<usize> = words.iter().filter_map(|(_, _, number)| *number).collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    let layout = if !words.is_empty() && numbers.len() == words.len() {
+        words.sort_by_key(|(_, _, number)| *number);
+        Layout::Numbered
+    } else {
+        grid_layout(&words)
+    };
+
+    let words = words
+        .iter()
+        .map(|(_, word, _)| word.nfkd().collect())
+        .collect();
+
+    Tokens {
+        words,
+        stripped,
+        layout,
+    }
+}
+
+/**
+ * Recognizes words laid out on several lines holding
+ * the same number of words
+ */
+fn grid_layout(words: &[(usize, &str, Option<usize>)]) -> Layout {
+    let mut lengths: Vec<usize> = Vec::new();
+    let mut previous: Option<usize> = None;
+    for (line, _, _) in words {
+        if previous == Some(*line) {
+            *lengths.last_mut().unwrap() += 1;
+        } else {
+            lengths.push(1);
+            previous = Some(*line);
+        }
+    }
+
+    let columns = lengths.first().copied().unwrap_or(0);
+    if lengths.len() > 1 && columns > 1 && lengths.iter().all(|&length| length == columns) {
+        Layout::Grid {
+            rows: lengths.len(),
+            columns,
+        }
+    } else {
+        Layout::Plain
+    }
+}
+
+#[test]
+fn test_tokenize() {
+    let tokens = tokenize("erupt  quit\tsphere\ntaxi,air, decade\u{a0}vote");
+    assert_eq!(
+        vec!["erupt", "quit", "sphere", "taxi", "air", "decade", "vote"],
+        tokens.words
+    );
+    assert!(tokens.stripped.is_empty());
+}
+
+#[test]
+fn test_tokenize_japanese() {
+    let tokens = tokenize("あいこくしん\u{3000}あいさつ\u{3000}あいだ");
+    assert_eq!(
+        vec!["あいこくしん", "あいさつ", "あいた\u{3099}"],
+        tokens.words
+    );
+}
+
+#[test]
+fn test_tokenize_numbered() {
+    let tokens = tokenize("1. erupt 2.quit 3) sphere #4 taxi 05: air 6 decade");
+    assert_eq!(
+        vec!["erupt", "quit", "sphere", "taxi", "air", "decade"],
+        tokens.words
+    );
+}
+
+#[test]
+fn test_tokenize_invisible() {
+    let tokens = tokenize("er\u{200b}upt \u{feff}quit");
+    assert_eq!(vec!["erupt", "quit"], tokens.words);
+    assert_eq!(
+        vec![
+            StrippedCharacter {
+                character: '\u{200b}',
+                position: 3
+            },
+            StrippedCharacter {
+                character: '\u{feff}',
+                position: 8
+            }
+        ],
+        tokens.stripped
+    );
+    assert_eq!(
+        "U+200B ZERO WIDTH SPACE at position 3",
+        tokens.stripped[0].to_string()
+    );
+}
+
+#[test]
+fn test_tokenize_numbered_out_of_order() {
+    let tokens = tokenize("3. sphere 1. erupt\n2. quit");
+    assert_eq!(vec!["erupt", "quit", "sphere"], tokens.words);
+    assert_eq!(Layout::Numbered, tokens.layout);
+
+    let tokens = tokenize("sphere 3 erupt 1 quit 2");
+    assert_eq!(vec!["erupt", "quit", "sphere"], tokens.words);
+    assert_eq!(Layout::Numbered, tokens.layout);
+}
+
+#[test]
+fn test_tokenize_grid() {
+    let tokens = tokenize("erupt sphere\nquit taxi\n");
+    assert_eq!(vec!["erupt", "sphere", "quit", "taxi"], tokens.words);
+    assert_eq!(
+        Layout::Grid {
+            rows: 2,
+            columns: 2
+        },
+        tokens.layout
+    );
+    assert_eq!(
+        Some(vec![
+            "erupt".to_string(),
+            "quit".to_string(),
+            "sphere".to_string(),
+            "taxi".to_string()
+        ]),
+        tokens.column_major()
+    );
+
+    // A numbered grid is sorted by its numbers
+    let tokens = tokenize("1. erupt 3. sphere\n2. quit 4. taxi");
+    assert_eq!(vec!["erupt", "quit", "sphere", "taxi"], tokens.words);
+    assert_eq!(None, tokens.column_major());
+
+    assert_eq!(Layout::Plain, tokenize("erupt quit\nsphere").layout);
+}
+
+#[test]
+fn test_tokenize_csv() {
+    let tokens = tokenize("\"erupt\";\"quit\";sphere\r\n");
+    assert_eq!(vec!["erupt", "quit", "sphere"], tokens.words);
+    assert_eq!(Layout::Plain, tokens.layout);
+
+    let tokens = tokenize("2,quit\n1,erupt\n");
+    assert_eq!(vec!["erupt", "quit"], tokens.words);
+    assert_eq!(Layout::Numbered, tokens.layout);
+}
@@ -0,0 +1,237 @@
+/**
+ * Rows of a QWERTY keyboard, each shifted half a key to the right
+ * of the row above
+ */
+const KEYBOARD: [&str; 4] = ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"];
+
+/**
+ * Mistakes looked for when typing a passphrase
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Typos {
+    /// Whole passphrase in lower, upper or title case, or one character of another case
+    pub case: bool,
+    /// One character replaced by a key next to it
+    pub adjacent_keys: bool,
+    /// One character dropped
+    pub dropped: bool,
+    /// One character doubled
+    pub doubled: bool,
+    /// Up to this number of digits appended
+    pub digits: u32,
+}
+
+impl Default for Typos {
+    fn default() -> Typos {
+        Typos {
+            case: true,
+            adjacent_keys: true,
+            dropped: true,
+            doubled: true,
+            digits: 1,
+        }
+    }
+}
+
+impl Typos {
+    /**
+     * Looks for no mistake
+     */
+    pub fn none() -> Typos {
+        Typos {
+            case: false,
+            adjacent_keys: false,
+            dropped: false,
+            doubled: false,
+            digits: 0,
+        }
+    }
+
+    /**
+     * Returns a passphrase followed by its variants with one mistake,
+     * each of them once
+     */
+    pub fn variants(&self, passphrase: &str) -> Vec<String> {
+        let chars: Vec<char> = passphrase.chars().collect();
+        let mut variants = vec![passphrase.to_string()];
+        let replaced = |position: usize, replacement: &[char]| -> String {
+            chars[..position]
+                .iter()
+                .chain(replacement)
+                .chain(&chars[position + 1..])
+                .collect()
+        };
+
+        if self.case {
+            variants.push(passphrase.to_lowercase());
+            variants.push(passphrase.to_uppercase());
+            variants.push(title_case(passphrase));
+            for (position, c) in chars.iter().enumerate() {
+                variants.push(replaced(position, &other_case(*c)));
+            }
+        }
+        if self.adjacent_keys {
+            for (position, c) in chars.iter().enumerate() {
+                for key in adjacent_keys(*c) {
+                    variants.push(replaced(position, &[key]));
+                }
+            }
+        }
+        if self.dropped {
+            for position in 0..chars.len() {
+                variants.push(replaced(position, &[]));
+            }
+        }
+        if self.doubled {
+            for (position, c) in chars.iter().enumerate() {
+                variants.push(replaced(position, &[*c, *c]));
+            }
+        }
+        for length in 1..=self.digits {
+            for number in 0..10u64.pow(length) {
+                variants.push(format!(
+                    "{}{:0width$}",
+                    passphrase,
+                    number,
+                    width = length as usize
+                ));
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        variants.retain(|variant| seen.insert(variant.clone()));
+        variants
+    }
+
+    /**
+     * Returns the passphrases of a list, one per line, followed by
+     * their variants, each of them once
+     */
+    pub fn candidates(&self, list: &str) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        list.lines()
+            .filter(|line| !line.is_empty())
+            .flat_map(|line| self.variants(line))
+            .filter(|candidate| seen.insert(candidate.clone()))
+            .collect()
+    }
+}
+
+fn title_case(passphrase: &str) -> String {
+    let mut chars = passphrase.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/**
+ * Returns a character in the other case, or as it is if it has no case
+ */
+fn other_case(c: char) -> Vec<char> {
+    match c.is_uppercase() {
+        true => c.to_lowercase().collect(),
+        false => c.to_uppercase().collect(),
+    }
+}
+
+/**
+ * Returns the keys around a key of a QWERTY keyboard, in the case of
+ * the key
+ */
+fn adjacent_keys(c: char) -> Vec<char> {
+    let lower = c.to_ascii_lowercase();
+    let (row, column) = match KEYBOARD
+        .iter()
+        .enumerate()
+        .find_map(|(row, keys)| keys.find(lower).map(|column| (row, column)))
+    {
+        Some(key) => key,
+        None => return vec![],
+    };
+
+    let mut keys = Vec::new();
+    let mut push = |row: usize, column: Option<usize>| {
+        if let Some(key) = column.and_then(|column| KEYBOARD[row].chars().nth(column)) {
+            keys.push(match c.is_ascii_uppercase() {
+                true => key.to_ascii_uppercase(),
+                false => key,
+            });
+        }
+    };
+    push(row, column.checked_sub(1));
+    push(row, Some(column + 1));
+    if row > 0 {
+        push(row - 1, Some(column));
+        push(row - 1, Some(column + 1));
+    }
+    if row + 1 < KEYBOARD.len() {
+        push(row + 1, column.checked_sub(1));
+        push(row + 1, Some(column));
+    }
+    keys
+}
+
+#[test]
+fn test_adjacent_keys() {
+    assert_eq!(vec!['f', 'h', 't', 'y', 'v', 'b'], adjacent_keys('g'));
+    assert_eq!(vec!['W', '1', '2', 'A'], adjacent_keys('Q'));
+    assert_eq!(vec!['n', ',', 'j', 'k'], adjacent_keys('m'));
+    assert_eq!(Vec::<char>::new(), adjacent_keys('é'));
+}
+
+#[test]
+fn test_variants() {
+    let typos = Typos {
+        digits: 0,
+        ..Typos::default()
+    };
+    let variants = typos.variants("Ab");
+    assert_eq!(
+        vec![
+            "Ab", "ab", "AB", // case
+            "Sb", "Qb", "Wb", "Zb", "Av", "An", "Ag", "Ah", // adjacent keys
+            "b", "A", // dropped
+            "AAb", "Abb" // doubled
+        ],
+        variants
+    );
+
+    let variants = Typos::none().variants("secret");
+    assert_eq!(vec!["secret"], variants);
+
+    let typos = Typos {
+        digits: 2,
+        ..Typos::none()
+    };
+    let variants = typos.variants("x");
+    assert_eq!(111, variants.len());
+    assert_eq!("x0", variants[1]);
+    assert_eq!("x99", variants[110]);
+
+    // The default finds all the mistakes of a typical passphrase
+    let variants = Typos::default().variants("correct horse");
+    for typo in [
+        "Correct horse",
+        "correct Horse",
+        "correct hprse",
+        "corect horse",
+        "correct horsee",
+        "correct horse7",
+    ]
+    .iter()
+    {
+        assert!(variants.contains(&typo.to_string()), "{}", typo);
+    }
+}
+
+#[test]
+fn test_candidates() {
+    let candidates = Typos::none().candidates("secret\n\nSecret\nsecret\n");
+    assert_eq!(vec!["secret", "Secret"], candidates);
+    let candidates = Typos::default().candidates("secret\nsecret\n");
+    assert_eq!(Typos::default().variants("secret"), candidates);
+}
@@ -0,0 +1,866 @@
+use crate::bytewords::{self, Style};
+use crate::encoding::crc32;
+use crate::error::Error;
+use crate::language::Language;
+use crate::mnemonic::Mnemonic;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/**
+ * Uniform Resource types of Blockchain Commons that hold a phrase :
+ * `crypto-seed` holds its entropy, `crypto-bip39` holds its words
+ * and their language.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrType {
+    Seed,
+    Bip39,
+}
+
+impl UrType {
+    pub const ALL: [UrType; 2] = [UrType::Seed, UrType::Bip39];
+
+    /**
+     * Returns the type written in UR strings
+     */
+    pub fn name(self) -> &'static str {
+        match self {
+            UrType::Seed => "crypto-seed",
+            UrType::Bip39 => "crypto-bip39",
+        }
+    }
+
+    fn from_name(name: &str) -> Result<UrType, Error> {
+        UrType::ALL
+            .iter()
+            .find(|ur_type| ur_type.name() == name)
+            .copied()
+            .ok_or_else(|| Error::UnsupportedUrType(name.to_string()))
+    }
+}
+
+/**
+ * Items of the CBOR subset used by URs
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Cbor {
+    Unsigned(u64),
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(Cbor, Cbor)>),
+    Tag(u64, Box<Cbor>),
+    Simple(u8),
+}
+
+/**
+ * Deepest nesting of CBOR items accepted when decoding
+ */
+const MAX_DEPTH: usize = 16;
+
+fn invalid_cbor() -> Error {
+    Error::InvalidUr("malformed CBOR content".to_string())
+}
+
+/**
+ * Writes the head of a CBOR item : its major type and an argument
+ * written in as few bytes as possible
+ */
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    if argument < 24 {
+        out.push(major | argument as u8);
+    } else if argument <= u8::MAX as u64 {
+        out.push(major | 24);
+        out.push(argument as u8);
+    } else if argument <= u16::MAX as u64 {
+        out.push(major | 25);
+        out.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u32::MAX as u64 {
+        out.push(major | 26);
+        out.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+impl Cbor {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Unsigned(value) => write_head(out, 0, *value),
+            Cbor::Negative(value) => write_head(out, 1, *value),
+            Cbor::Bytes(bytes) => {
+                write_head(out, 2, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Text(text) => {
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Cbor::Array(items) => {
+                write_head(out, 4, items.len() as u64);
+                for item in items {
+                    item.encode(out);
+                }
+            }
+            Cbor::Map(entries) => {
+                write_head(out, 5, entries.len() as u64);
+                for (key, value) in entries {
+                    key.encode(out);
+                    value.encode(out);
+                }
+            }
+            Cbor::Tag(tag, item) => {
+                write_head(out, 6, *tag);
+                item.encode(out);
+            }
+            Cbor::Simple(value) => write_head(out, 7, *value as u64),
+        }
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /**
+     * Decodes a single CBOR item filling all the bytes
+     */
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Cbor, Error> {
+        let mut reader = CborReader { bytes, position: 0 };
+        let item = reader.read_item(0)?;
+        if reader.position != bytes.len() {
+            return Err(invalid_cbor());
+        }
+        Ok(item)
+    }
+
+    /**
+     * Returns the value of an integer key of a map
+     */
+    fn get(&self, key: u64) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries
+                .iter()
+                .find(|(candidate, _)| *candidate == Cbor::Unsigned(key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+struct CborReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(invalid_cbor)?;
+        let taken = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(taken)
+    }
+
+    fn read_head(&mut self) -> Result<(u8, u64), Error> {
+        let initial = self.take(1)?[0];
+        let argument = match initial & 0x1f {
+            value @ 0..=23 => value as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => self.take(2)?.iter().fold(0, |acc, b| acc << 8 | *b as u64),
+            26 => self.take(4)?.iter().fold(0, |acc, b| acc << 8 | *b as u64),
+            27 => self.take(8)?.iter().fold(0, |acc, b| acc << 8 | *b as u64),
+            _ => return Err(invalid_cbor()),
+        };
+        Ok((initial >> 5, argument))
+    }
+
+    /**
+     * Length of a string or container, which can't be longer than the
+     * remaining bytes as each element takes at least one byte
+     */
+    fn read_length(&self, argument: u64) -> Result<usize, Error> {
+        if argument > (self.bytes.len() - self.position) as u64 {
+            return Err(invalid_cbor());
+        }
+        Ok(argument as usize)
+    }
+
+    fn read_item(&mut self, depth: usize) -> Result<Cbor, Error> {
+        if depth > MAX_DEPTH {
+            return Err(invalid_cbor());
+        }
+        let (major, argument) = self.read_head()?;
+        Ok(match major {
+            0 => Cbor::Unsigned(argument),
+            1 => Cbor::Negative(argument),
+            2 => {
+                let length = self.read_length(argument)?;
+                Cbor::Bytes(self.take(length)?.to_vec())
+            }
+            3 => {
+                let length = self.read_length(argument)?;
+                let text = std::str::from_utf8(self.take(length)?).map_err(|_| invalid_cbor())?;
+                Cbor::Text(text.to_string())
+            }
+            4 => {
+                let length = self.read_length(argument)?;
+                let items = (0..length)
+                    .map(|_| self.read_item(depth + 1))
+                    .collect::<Result<Vec<Cbor>, Error>>()?;
+                Cbor::Array(items)
+            }
+            5 => {
+                let length = self.read_length(argument)?;
+                let entries = (0..length)
+                    .map(|_| Ok((self.read_item(depth + 1)?, self.read_item(depth + 1)?)))
+                    .collect::<Result<Vec<(Cbor, Cbor)>, Error>>()?;
+                Cbor::Map(entries)
+            }
+            6 => Cbor::Tag(argument, Box::new(self.read_item(depth + 1)?)),
+            _ if argument < 24 => Cbor::Simple(argument as u8),
+            _ => return Err(invalid_cbor()),
+        })
+    }
+}
+
+/**
+ * Xoshiro256** generator seeded with the SHA-256 of some bytes, which
+ * chooses the fragments mixed into each part of a multipart UR
+ */
+struct Xoshiro {
+    state: [u64; 4],
+}
+
+impl Xoshiro {
+    fn new(seed: &[u8]) -> Xoshiro {
+        let hash = Sha256::digest(seed);
+        let mut state = [0u64; 4];
+        for (index, chunk) in hash.chunks(8).enumerate() {
+            state[index] = chunk.iter().fold(0, |acc, b| acc << 8 | *b as u64);
+        }
+        Xoshiro { state }
+    }
+
+    fn next(&mut self) -> u64 {
+        let state = &mut self.state;
+        let result = state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let shifted = state[1] << 17;
+        state[2] ^= state[0];
+        state[3] ^= state[1];
+        state[1] ^= state[2];
+        state[0] ^= state[3];
+        state[2] ^= shifted;
+        state[3] = state[3].rotate_left(45);
+        result
+    }
+
+    fn next_double(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_int(&mut self, low: usize, high: usize) -> usize {
+        (self.next_double() * (high - low + 1) as f64) as usize + low
+    }
+
+    /**
+     * Draws `count` distinct items
+     */
+    fn shuffled<T>(&mut self, mut items: Vec<T>, count: usize) -> Vec<T> {
+        let mut shuffled = Vec::with_capacity(count);
+        while shuffled.len() < count && !items.is_empty() {
+            let index = self.next_int(0, items.len() - 1);
+            shuffled.push(items.remove(index));
+        }
+        shuffled
+    }
+
+    /**
+     * Draws how many fragments are mixed into a part, from 1 to `count`
+     * with weights 1/1, 1/2... 1/count, using Vose's alias method
+     */
+    fn choose_degree(&mut self, count: usize) -> usize {
+        let sum: f64 = (1..=count).map(|degree| 1.0 / degree as f64).sum();
+        let mut weights: Vec<f64> = (1..=count)
+            .map(|degree| count as f64 / (degree as f64 * sum))
+            .collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..count).rev().partition(|index| weights[*index] < 1.0);
+
+        let mut probabilities = vec![1.0; count];
+        let mut aliases = vec![0; count];
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+            probabilities[less] = weights[less];
+            aliases[less] = more;
+            weights[more] += weights[less] - 1.0;
+            if weights[more] < 1.0 {
+                small.push(more);
+            } else {
+                large.push(more);
+            }
+        }
+
+        let column = (count as f64 * self.next_double()) as usize;
+        let toss = self.next_double();
+        if toss < probabilities[column] {
+            column + 1
+        } else {
+            aliases[column] + 1
+        }
+    }
+}
+
+/**
+ * Returns the indexes of the fragments XORed into a part : the parts
+ * of the first cycle hold a single fragment each, the following ones
+ * mix randomly chosen fragments
+ */
+fn choose_fragments(sequence: usize, count: usize, checksum: u32) -> Vec<usize> {
+    if sequence <= count {
+        return vec![sequence - 1];
+    }
+    let mut seed = (sequence as u32).to_be_bytes().to_vec();
+    seed.extend_from_slice(&checksum.to_be_bytes());
+    let mut xoshiro = Xoshiro::new(&seed);
+    let degree = xoshiro.choose_degree(count);
+    xoshiro.shuffled((0..count).collect(), degree)
+}
+
+fn xor(target: &mut [u8], other: &[u8]) {
+    for (byte, other) in target.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+}
+
+/**
+ * A part of a multipart UR, as defined by the fountain codes of BCR-2020-005
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Part {
+    sequence: usize,
+    count: usize,
+    message_length: usize,
+    checksum: u32,
+    data: Vec<u8>,
+}
+
+impl Part {
+    fn to_cbor(&self) -> Cbor {
+        Cbor::Array(vec![
+            Cbor::Unsigned(self.sequence as u64),
+            Cbor::Unsigned(self.count as u64),
+            Cbor::Unsigned(self.message_length as u64),
+            Cbor::Unsigned(self.checksum as u64),
+            Cbor::Bytes(self.data.clone()),
+        ])
+    }
+
+    fn from_cbor(cbor: &Cbor) -> Result<Part, Error> {
+        let invalid = || Error::InvalidUr("malformed part".to_string());
+        let items = match cbor {
+            Cbor::Array(items) if items.len() == 5 => items,
+            _ => return Err(invalid()),
+        };
+        let number = |index: usize| match items[index] {
+            Cbor::Unsigned(value) if value <= u32::MAX as u64 => Ok(value),
+            _ => Err(invalid()),
+        };
+        let part = Part {
+            sequence: number(0)? as usize,
+            count: number(1)? as usize,
+            message_length: number(2)? as usize,
+            checksum: number(3)? as u32,
+            data: match &items[4] {
+                Cbor::Bytes(data) => data.clone(),
+                _ => return Err(invalid()),
+            },
+        };
+        if part.sequence == 0
+            || part.count == 0
+            || part.data.is_empty()
+            || part.message_length > part.count * part.data.len()
+            // Fragments are as long as they can be, so a part claiming more
+            // of them than the message holds is forged
+            || part.count > part.message_length.max(1).div_ceil(part.data.len())
+        {
+            return Err(invalid());
+        }
+        Ok(part)
+    }
+
+    fn fragments(&self) -> Vec<usize> {
+        choose_fragments(self.sequence, self.count, self.checksum)
+    }
+}
+
+/**
+ * Encodes a CBOR message as a single part UR
+ */
+pub fn encode(ur_type: &str, message: &[u8]) -> String {
+    format!(
+        "ur:{}/{}",
+        ur_type,
+        bytewords::encode(message, Style::Minimal)
+    )
+}
+
+/**
+ * Encodes a CBOR message as `parts` parts of a multipart UR, for an
+ * animated QR code. The message is split into fragments of at most
+ * `max_fragment_length` bytes : the first parts hold one fragment
+ * each, the following ones XOR several of them so that a reader can
+ * recover missed frames. At least one part per fragment is returned.
+ */
+pub fn encode_parts(
+    ur_type: &str,
+    message: &[u8],
+    max_fragment_length: usize,
+    parts: usize,
+) -> Vec<String> {
+    let max_fragment_length = max_fragment_length.max(1);
+    let count = message.len().max(1).div_ceil(max_fragment_length);
+    let fragment_length = message.len().max(1).div_ceil(count);
+    let mut padded = message.to_vec();
+    padded.resize(count * fragment_length, 0);
+    let fragments: Vec<&[u8]> = padded.chunks(fragment_length).collect();
+    let checksum = crc32(message);
+
+    (1..=parts.max(count))
+        .map(|sequence| {
+            let mut data = vec![0u8; fragment_length];
+            for index in choose_fragments(sequence, count, checksum) {
+                xor(&mut data, fragments[index]);
+            }
+            let part = Part {
+                sequence,
+                count,
+                message_length: message.len(),
+                checksum,
+                data,
+            };
+            format!(
+                "ur:{}/{}-{}/{}",
+                ur_type,
+                sequence,
+                count,
+                bytewords::encode(&part.to_cbor().to_bytes(), Style::Minimal)
+            )
+        })
+        .collect()
+}
+
+/**
+ * Sequence number and count of a part of a multipart UR
+ */
+type Sequence = Option<(usize, usize)>;
+
+/**
+ * Splits a UR string into its type, its sequence if it is a part,
+ * and the bytes held by its Bytewords
+ */
+fn parse(ur: &str) -> Result<(String, Sequence, Vec<u8>), Error> {
+    let lowercase = ur.trim().to_lowercase();
+    let body = lowercase
+        .strip_prefix("ur:")
+        .ok_or_else(|| Error::InvalidUr("a UR must start with ur:".to_string()))?;
+    let components: Vec<&str> = body.split('/').collect();
+    let ur_type = components[0];
+    if ur_type.is_empty()
+        || !ur_type
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(Error::InvalidUr(format!("{} is not a valid type", ur_type)));
+    }
+
+    match components[1..] {
+        [payload] => Ok((
+            ur_type.to_string(),
+            None,
+            bytewords::decode(payload, Style::Minimal)?,
+        )),
+        [sequence, payload] => {
+            let numbers: Vec<usize> = sequence
+                .split('-')
+                .map(|number| number.parse().ok().filter(|number| *number > 0))
+                .collect::<Option<Vec<usize>>>()
+                .filter(|numbers| numbers.len() == 2)
+                .ok_or_else(|| Error::InvalidUr(format!("{} is not a valid sequence", sequence)))?;
+            Ok((
+                ur_type.to_string(),
+                Some((numbers[0], numbers[1])),
+                bytewords::decode(payload, Style::Minimal)?,
+            ))
+        }
+        _ => Err(Error::InvalidUr("unexpected path components".to_string())),
+    }
+}
+
+/**
+ * Recovers the message of a multipart UR from its parts, solving the
+ * mixed ones with the fragments already known
+ */
+fn join(parts: &[Part]) -> Result<Vec<u8>, Error> {
+    let first = &parts[0];
+    if parts.iter().any(|part| {
+        part.count != first.count
+            || part.message_length != first.message_length
+            || part.checksum != first.checksum
+            || part.data.len() != first.data.len()
+    }) {
+        return Err(Error::InvalidUr(
+            "parts belong to different messages".to_string(),
+        ));
+    }
+
+    let mut decoded: BTreeMap<usize, Vec<u8>> = BTreeMap::new();
+    let mut pending: Vec<(BTreeSet<usize>, Vec<u8>)> = parts
+        .iter()
+        .map(|part| (part.fragments().into_iter().collect(), part.data.clone()))
+        .collect();
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for (fragments, data) in pending.iter_mut() {
+            let known: Vec<usize> = fragments
+                .iter()
+                .filter(|index| decoded.contains_key(index))
+                .copied()
+                .collect();
+            for index in known {
+                xor(data, &decoded[&index]);
+                fragments.remove(&index);
+            }
+            if fragments.len() == 1 {
+                decoded.insert(*fragments.iter().next().unwrap(), data.clone());
+                fragments.clear();
+                progress = true;
+            }
+        }
+        pending.retain(|(fragments, _)| !fragments.is_empty());
+    }
+
+    if decoded.len() < first.count {
+        return Err(Error::IncompleteUr {
+            found: decoded.len(),
+            expected: first.count,
+        });
+    }
+    let mut message: Vec<u8> = decoded.into_values().flatten().collect();
+    if message[first.message_length..]
+        .iter()
+        .any(|byte| *byte != 0)
+        || crc32(&message[..first.message_length]) != first.checksum
+    {
+        return Err(Error::InvalidUr(
+            "checksum of the message does not match".to_string(),
+        ));
+    }
+    message.truncate(first.message_length);
+    Ok(message)
+}
+
+/**
+ * Decodes a single part UR, or the parts of a multipart UR given in any
+ * order, into its type and CBOR message. The CRC-32 of each part's
+ * Bytewords and of the whole message are checked.
+ */
+pub fn decode(urs: &[&str]) -> Result<(String, Vec<u8>), Error> {
+    let mut ur_type = None;
+    let mut parts = Vec::new();
+    for ur in urs {
+        let (part_type, sequence, payload) = parse(ur)?;
+        if ur_type.get_or_insert_with(|| part_type.clone()) != &part_type {
+            return Err(Error::InvalidUr("parts have different types".to_string()));
+        }
+        match sequence {
+            None if urs.len() == 1 => return Ok((part_type, payload)),
+            None => {
+                return Err(Error::InvalidUr(
+                    "a single part UR can't be combined with others".to_string(),
+                ))
+            }
+            Some((sequence, count)) => {
+                let part = Part::from_cbor(&Cbor::from_bytes(&payload)?)?;
+                if (part.sequence, part.count) != (sequence, count) {
+                    return Err(Error::InvalidUr(format!(
+                        "sequence {}-{} does not match its content",
+                        sequence, count
+                    )));
+                }
+                parts.push(part);
+            }
+        }
+    }
+
+    match ur_type {
+        Some(ur_type) => Ok((ur_type, join(&parts)?)),
+        None => Err(Error::InvalidUr("no part provided".to_string())),
+    }
+}
+
+/**
+ * Returns the code of a language in `crypto-bip39`
+ */
+pub fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::English => "en",
+        Language::French => "fr",
+        Language::Czech => "cs",
+        Language::Italian => "it",
+        Language::Spanish => "es",
+        Language::Portuguese => "pt",
+        Language::Japanese => "ja",
+        Language::Korean => "ko",
+        Language::ChineseTraditional => "zh-Hant",
+        Language::ChineseSimplified => "zh-Hans",
+    }
+}
+
+/**
+ * Returns the CBOR message of a phrase : its entropy for `crypto-seed`,
+ * its words and language for `crypto-bip39`
+ */
+pub fn to_message(
+    mnemonic: &Mnemonic,
+    language: Language,
+    ur_type: UrType,
+) -> Result<Vec<u8>, Error> {
+    let entropy = mnemonic.to_entropy(&language)?;
+    let cbor = match ur_type {
+        UrType::Seed => Cbor::Map(vec![(Cbor::Unsigned(1), Cbor::Bytes(entropy))]),
+        UrType::Bip39 => Cbor::Map(vec![
+            (
+                Cbor::Unsigned(1),
+                Cbor::Array(
+                    mnemonic
+                        .words()
+                        .iter()
+                        .map(|word| Cbor::Text(word.clone()))
+                        .collect(),
+                ),
+            ),
+            (
+                Cbor::Unsigned(2),
+                Cbor::Text(language_code(language).to_string()),
+            ),
+        ]),
+    };
+    Ok(cbor.to_bytes())
+}
+
+/**
+ * Reads the phrase held by the CBOR message of a UR. A `crypto-seed`
+ * has no language and gives an english phrase ; the checksum of a
+ * `crypto-bip39` phrase must be valid.
+ */
+pub fn from_message(ur_type: &str, message: &[u8]) -> Result<(Mnemonic, Language), Error> {
+    let ur_type = UrType::from_name(ur_type)?;
+    let cbor = Cbor::from_bytes(message)?;
+    let missing =
+        |field: &str| Error::InvalidUr(format!("{} {} is missing", ur_type.name(), field));
+
+    match ur_type {
+        UrType::Seed => match cbor.get(1) {
+            Some(Cbor::Bytes(entropy)) => Ok((
+                Mnemonic::from_entropy(entropy, &Language::English)?,
+                Language::English,
+            )),
+            _ => Err(missing("payload")),
+        },
+        UrType::Bip39 => {
+            let words = match cbor.get(1) {
+                Some(Cbor::Array(words)) => words
+                    .iter()
+                    .map(|word| match word {
+                        Cbor::Text(word) => Some(word.clone()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<String>>>()
+                    .ok_or_else(|| missing("words"))?,
+                _ => return Err(missing("words")),
+            };
+            let language = match cbor.get(2) {
+                None => Language::English,
+                Some(Cbor::Text(code)) => Language::ALL
+                    .iter()
+                    .find(|language| language_code(**language).eq_ignore_ascii_case(code))
+                    .copied()
+                    .ok_or_else(|| Error::UnknownLanguage(code.clone()))?,
+                Some(_) => return Err(missing("language")),
+            };
+            let mnemonic = Mnemonic::from_words(words);
+            mnemonic.verify_checksum(&language)?;
+            Ok((mnemonic, language))
+        }
+    }
+}
+
+/**
+ * Decodes the phrase held by a single part UR, or by the parts of
+ * a multipart UR
+ */
+pub fn decode_phrase(urs: &[&str]) -> Result<(Mnemonic, Language), Error> {
+    let (ur_type, message) = decode(urs)?;
+    from_message(&ur_type, &message)
+}
+
+#[cfg(test)]
+fn make_message(seed: &str, length: usize) -> Vec<u8> {
+    let mut xoshiro = Xoshiro::new(seed.as_bytes());
+    (0..length)
+        .map(|_| xoshiro.next_int(0, 255) as u8)
+        .collect()
+}
+
+#[test]
+fn test_xoshiro() {
+    let mut xoshiro = Xoshiro::new(b"Wolf");
+    let expected = [
+        42, 81, 85, 8, 82, 84, 76, 73, 70, 88, 2, 74, 40, 48, 77, 54, 88, 7, 5, 88,
+    ];
+    for value in expected.iter() {
+        assert_eq!(*value, xoshiro.next() % 100);
+    }
+
+    let mut xoshiro = Xoshiro::new(b"Wolf");
+    assert_eq!(
+        vec![6, 4, 9, 3, 10, 5, 7, 8, 1, 2],
+        xoshiro.shuffled((1..=10).collect(), 10)
+    );
+}
+
+#[test]
+fn test_cbor() {
+    let cbor = Cbor::Map(vec![
+        (Cbor::Unsigned(1), Cbor::Bytes(vec![0xab; 30])),
+        (
+            Cbor::Unsigned(2),
+            Cbor::Tag(100, Box::new(Cbor::Unsigned(18394))),
+        ),
+        (Cbor::Unsigned(3), Cbor::Text("wallet".to_string())),
+    ]);
+    let bytes = cbor.to_bytes();
+    assert_eq!([0xa3, 0x01, 0x58, 0x1e], bytes[..4]);
+    assert_eq!(Ok(cbor), Cbor::from_bytes(&bytes));
+
+    assert!(Cbor::from_bytes(&[0x58, 0x1e, 0xab]).is_err());
+    assert!(Cbor::from_bytes(&[0x01, 0x02]).is_err());
+    assert!(Cbor::from_bytes(&[0x81; 40]).is_err());
+}
+
+#[test]
+fn test_single_part() {
+    // Vector of the ur crate : a CBOR byte string of 50 bytes
+    let mut message = vec![0x58, 0x32];
+    message.extend(make_message("Wolf", 50));
+    let ur = encode("bytes", &message);
+    assert_eq!(
+        "ur:bytes/hdeymejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmhhpktpmsrjtgwdpfnsboxgwlbaawzu\
+         efywkdplrsrjynbvygabwjldapfcsdwkbrkch",
+        ur
+    );
+    assert_eq!(
+        Ok(("bytes".to_string(), message)),
+        decode(&[&ur.to_uppercase()])
+    );
+}
+
+#[test]
+fn test_multipart() {
+    // Vector of the ur crate : a CBOR byte string of 256 bytes
+    let mut message = vec![0x59, 0x01, 0x00];
+    message.extend(make_message("Wolf", 256));
+    let parts = encode_parts("bytes", &message, 30, 20);
+    assert_eq!(20, parts.len());
+    assert_eq!(
+        "ur:bytes/1-9/lpadascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydmmh\
+         hpktpmsrjtdkgslpgh",
+        parts[0]
+    );
+    assert_eq!(
+        "ur:bytes/10-9/lpbkascfadaxcywenbpljkhdcahkadaemejtswhhylkepmykhhtsytsnoyoyaxaedsuttydm\
+         mhhpktpmsrjtwdkiplzs",
+        parts[9]
+    );
+    assert_eq!(
+        "ur:bytes/20-9/lpbbascfadaxcywenbpljkhdcayapmrleeleaxpasfrtrdkncffwjyjzgyetdmlewtkpktgl\
+         lepfrltataztksmhkbot",
+        parts[19]
+    );
+
+    let all: Vec<&str> = parts.iter().map(String::as_str).collect();
+    let expected = Ok(("bytes".to_string(), message));
+    assert_eq!(expected, decode(&all[..9]));
+    // Missed frames are recovered from the mixed parts
+    assert_eq!(expected, decode(&all[3..]));
+    assert_eq!(
+        Err(Error::IncompleteUr {
+            found: 8,
+            expected: 9
+        }),
+        decode(&all[1..9])
+    );
+}
+
+#[test]
+fn test_phrase() {
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                  abandon about";
+    let mnemonic = Mnemonic::parse(phrase);
+
+    for ur_type in UrType::ALL.iter() {
+        let message = to_message(&mnemonic, Language::English, *ur_type).unwrap();
+        let ur = encode(ur_type.name(), &message);
+        assert_eq!(
+            Ok((mnemonic.clone(), Language::English)),
+            decode_phrase(&[&ur])
+        );
+        let parts = encode_parts(ur_type.name(), &message, 10, 0);
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+        assert_eq!(
+            Ok((mnemonic.clone(), Language::English)),
+            decode_phrase(&parts)
+        );
+    }
+
+    let message = to_message(&mnemonic, Language::English, UrType::Seed).unwrap();
+    assert_eq!(
+        "ur:crypto-seed/oyadgdaeaeaeaeaeaeaeaeaeaeaeaeaeaeaeaebbftpmcw",
+        encode(UrType::Seed.name(), &message)
+    );
+    assert_eq!(
+        Err(Error::UnsupportedUrType("bytes".to_string())),
+        from_message("bytes", &message)
+    );
+}
+
+#[test]
+fn test_invalid_ur() {
+    assert!(matches!(
+        decode(&["crypto-seed/oyadgdaeaeaeaeaeaeaeaeaeaeaeaeaeaeaeaebbftpmcw"]),
+        Err(Error::InvalidUr(_))
+    ));
+    assert_eq!(
+        Err(Error::InvalidBytewordsChecksum),
+        decode(&["ur:crypto-seed/oyadgdaeaeaeaeaeaeaeaeaeaeaeaeaeaeaeaebbftpmcx"])
+    );
+    assert!(matches!(
+        decode(&["ur:bytes/0-9/aeadaolazmjendeoti"]),
+        Err(Error::InvalidUr(_))
+    ));
+    // A huge fragment count for a short message is refused before decoding
+    assert!(matches!(
+        decode(&["ur:bytes/4294967294-4294967280/lpcyzmzmzmzecyzmzmzmwtadcyaeaeaatdfpaeuyischsp"]),
+        Err(Error::InvalidUr(_))
+    ));
+}
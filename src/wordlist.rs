@@ -0,0 +1,294 @@
+use crate::electrum::ElectrumOldWordlist;
+use crate::error::Error;
+use crate::language::Language;
+use crate::monero::{MoneroWordlist, SEED_WORDS};
+use crate::slip39::Slip39Wordlist;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+
+/**
+ * A list of words a mnemonic phrase can be checked against.
+ * Implemented by the built-in dictionaries and by dictionary files
+ * provided by user, so every check works the same way with both.
+ */
+pub trait Wordlist: Send + Sync {
+    /**
+     * Returns a human readable name for the dictionary
+     */
+    fn name(&self) -> String;
+
+    /**
+     * Returns the language of the dictionary, if known
+     */
+    fn language(&self) -> Option<Language>;
+
+    /**
+     * Returns the number of words of the dictionary
+     */
+    fn len(&self) -> usize;
+
+    /**
+     * Returns the word at the given index
+     */
+    fn word(&self, index: usize) -> Option<&str>;
+
+    /**
+     * Returns the index of a word in the dictionary
+     */
+    fn index_of(&self, word: &str) -> Option<usize>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * Checks if a word is part of the dictionary
+     */
+    fn contains(&self, word: &str) -> bool {
+        self.index_of(word).is_some()
+    }
+
+    /**
+     * Returns the words of the dictionary starting with the given prefix
+     */
+    fn words_with_prefix(&self, prefix: &str) -> Vec<&str> {
+        (0..self.len())
+            .filter_map(|index| self.word(index))
+            .filter(|word| word.starts_with(prefix))
+            .collect()
+    }
+}
+
+impl Wordlist for Language {
+    fn name(&self) -> String {
+        Language::name(*self).to_string()
+    }
+
+    fn language(&self) -> Option<Language> {
+        Some(*self)
+    }
+
+    fn len(&self) -> usize {
+        self.words().len()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words().get(index).copied()
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        builtin_indexes()[self].get(word).copied()
+    }
+}
+
+/**
+ * Returns the index of every word of the built-in dictionaries,
+ * built on first use
+ */
+fn builtin_indexes() -> &'static HashMap<Language, HashMap<&'static str, usize>> {
+    static INDEXES: OnceLock<HashMap<Language, HashMap<&'static str, usize>>> = OnceLock::new();
+    INDEXES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|&language| {
+                let indexes = language
+                    .words()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &word)| (word, index))
+                    .collect();
+                (language, indexes)
+            })
+            .collect()
+    })
+}
+
+/**
+ * Returns the built-in BIP-39 dictionaries
+ */
+pub fn builtin_wordlists() -> Vec<Box<dyn Wordlist>> {
+    Language::ALL
+        .iter()
+        .map(|&language| Box::new(language) as Box<dyn Wordlist>)
+        .collect()
+}
+
+/**
+ * A seed scheme other than BIP-39 with a built-in dictionary.
+ * Phrases of these schemes are decoded apart from the BIP-39 check.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Seeds of Electrum before version 2.0
+    ElectrumOld,
+    /// Shares of a SLIP-39 backup
+    Slip39,
+    /// Monero seeds, in one of their languages
+    Monero(MoneroWordlist),
+}
+
+impl Scheme {
+    /**
+     * Returns the dictionary of the scheme
+     */
+    pub fn wordlist(self) -> Box<dyn Wordlist> {
+        match self {
+            Scheme::ElectrumOld => Box::new(ElectrumOldWordlist),
+            Scheme::Slip39 => Box::new(Slip39Wordlist),
+            Scheme::Monero(wordlist) => Box::new(wordlist),
+        }
+    }
+
+    /**
+     * Returns the numbers of words of the phrases of the scheme
+     */
+    pub fn word_counts(self) -> &'static [usize] {
+        match self {
+            Scheme::ElectrumOld => &[12, 24],
+            Scheme::Slip39 => &[20, 33],
+            Scheme::Monero(_) => &[SEED_WORDS, SEED_WORDS + 1],
+        }
+    }
+
+    /**
+     * Returns true if all the words are in the dictionary of the scheme
+     */
+    pub fn contains_all(self, words: &[String]) -> bool {
+        let wordlist = self.wordlist();
+        !words.is_empty() && words.iter().all(|word| wordlist.contains(word))
+    }
+}
+
+/**
+ * Returns the seed schemes other than BIP-39 with a built-in dictionary
+ */
+pub fn builtin_schemes() -> Vec<Scheme> {
+    let mut schemes = vec![Scheme::ElectrumOld, Scheme::Slip39];
+    schemes.extend(
+        MoneroWordlist::ALL
+            .iter()
+            .map(|&wordlist| Scheme::Monero(wordlist)),
+    );
+    schemes
+}
+
+/**
+ * A dictionary loaded from a file provided by user
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalWordlist {
+    path: String,
+    language: Option<Language>,
+    words: Vec<String>,
+    indexes: HashMap<String, usize>,
+}
+
+impl ExternalWordlist {
+    /**
+     * Loads an external dictionary file.
+     * The file is expected to contain one word per line.
+     * Will return an Err if path is invalid.
+     */
+    pub fn load(path: &str) -> Result<ExternalWordlist, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(ExternalWordlist::from_content(path, &content)),
+            Err(_) => Err(Error::DictionaryLoad(path.to_string())),
+        }
+    }
+
+    /**
+     * Builds an external dictionary from the content of a file
+     */
+    pub fn from_content(path: &str, content: &str) -> ExternalWordlist {
+        let words: Vec<String> = content
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let mut indexes = HashMap::new();
+        for (index, word) in words.iter().enumerate() {
+            indexes.entry(word.clone()).or_insert(index);
+        }
+
+        ExternalWordlist {
+            path: path.to_string(),
+            language: None,
+            words,
+            indexes,
+        }
+    }
+
+    /**
+     * Sets the language the dictionary is written in
+     */
+    pub fn with_language(mut self, language: Language) -> ExternalWordlist {
+        self.language = Some(language);
+        self
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Wordlist for ExternalWordlist {
+    fn name(&self) -> String {
+        self.path.clone()
+    }
+
+    fn language(&self) -> Option<Language> {
+        self.language
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).map(String::as_str)
+    }
+
+    fn index_of(&self, word: &str) -> Option<usize> {
+        self.indexes.get(word).copied()
+    }
+}
+
+#[test]
+fn test_builtin_wordlist() {
+    let english: &dyn Wordlist = &Language::English;
+    assert!(english.contains("erupt"));
+    assert!(!english.contains("cswisafraud"));
+    assert_eq!(Some(0), english.index_of("abandon"));
+    assert_eq!(Some("zoo"), english.word(2047));
+    assert_eq!(None, english.word(2048));
+    assert_eq!(
+        vec!["abandon", "ability", "able"],
+        english.words_with_prefix("ab")[..3]
+    );
+    assert_eq!(10, builtin_wordlists().len());
+    assert_eq!(3, builtin_schemes().len());
+    let words = vec!["hardly".to_string(), "hallway".to_string()];
+    assert!(Scheme::ElectrumOld.contains_all(&words));
+    assert!(!Scheme::Slip39.contains_all(&words));
+    assert_eq!("slip39", Scheme::Slip39.wordlist().name());
+}
+
+#[test]
+fn test_load_dictionary() {
+    let invalid_path: &str = "resources/test/invalid_dictionary.txt";
+    assert_eq!(
+        Err(Error::DictionaryLoad(invalid_path.to_string())),
+        ExternalWordlist::load(invalid_path)
+    );
+
+    let valid_path: &str = "resources/test/test_dictionary.txt";
+    let result = ExternalWordlist::load(valid_path);
+    assert!(result.is_ok());
+    let wordlist = result.unwrap().with_language(Language::French);
+    assert!(wordlist.contains("loterie"));
+    assert_eq!(2048, wordlist.len());
+    assert_eq!(Some(Language::French), wordlist.language());
+    assert_eq!(Some(2047), wordlist.index_of("zoologie"));
+}